@@ -0,0 +1,36 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod transfer_hook;
+
+use transfer_hook::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("2FkdJLYh5Y2sD6n4MjfLg12BvaXwx7rWUU6zwU62CGY6");
+
+// Token-2022 invokes the hook while rwa_contract is still on the stack, so the hook has to live
+// in its own program; the runtime rejects a program re-entering itself through a CPI
+#[program]
+pub mod credit_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        transfer_hook::initialize_extra_account_meta_list(ctx)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        transfer_hook::transfer_hook(ctx, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn matches_the_hook_program_rwa_contract_targets() {
+        assert_eq!(crate::ID, rwa_contract::transfer_hook::TRANSFER_HOOK_PROGRAM_ID);
+    }
+}
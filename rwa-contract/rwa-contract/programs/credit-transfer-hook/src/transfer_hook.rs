@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as SplTokenAccount;
+use anchor_spl::token_interface::{TokenAccount, Mint};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use rwa_contract::errors::ErrorCode;
+use rwa_contract::industry::load_industry;
+use rwa_contract::program::RwaContract;
+use rwa_contract::state::{CarbonToken, ComplianceStatus};
+
+pub const EXTRA_ACCOUNT_METAS_COUNT: usize = 7;
+
+// The list is fixed by this program, so anyone may pay to create it for a carbon credit mint
+pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+    // Token-2022 resolves rwa_contract (index 5), the owners' Industry and ApprovedTrader PDAs
+    // under it and the owners themselves from the source (index 0) and destination (index 2)
+    // token accounts
+    let metas = [
+        ExtraAccountMeta::new_with_pubkey(&rwa_contract::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"industry".to_vec() },
+                Seed::AccountData { account_index: 0, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"industry".to_vec() },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_pubkey_data(
+            &PubkeyData::AccountData { account_index: 0, data_index: 32 },
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_pubkey_data(
+            &PubkeyData::AccountData { account_index: 2, data_index: 32 },
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"approved_trader".to_vec() },
+                Seed::AccountData { account_index: 0, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"approved_trader".to_vec() },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            false,
+        )?,
+    ];
+
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+        &metas,
+    )?;
+
+    Ok(())
+}
+
+pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+    assert_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+    // Clawbacks move credits out of holders that are no longer approved
+    let (carbon_token, _) = Pubkey::find_program_address(
+        &[b"carbon_token", ctx.accounts.mint.key().as_ref()],
+        &rwa_contract::ID,
+    );
+    let (credit_authority, _) = Pubkey::find_program_address(
+        &[b"credit_authority", carbon_token.as_ref()],
+        &rwa_contract::ID,
+    );
+
+    require!(
+        ctx.accounts.authority.key() == credit_authority
+            || is_approved_holder(
+                &ctx.accounts.source_owner,
+                &ctx.accounts.source_industry,
+                &ctx.accounts.source_trader,
+                false,
+            )?,
+        ErrorCode::HolderNotApproved
+    );
+    require!(
+        is_approved_holder(
+            &ctx.accounts.destination_owner,
+            &ctx.accounts.destination_industry,
+            &ctx.accounts.destination_trader,
+            true,
+        )?,
+        ErrorCode::HolderNotApproved
+    );
+
+    Ok(())
+}
+
+fn assert_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<SplTokenAccount>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), ErrorCode::NotTransferring);
+    Ok(())
+}
+
+fn is_approved_holder(
+    owner: &AccountInfo,
+    industry: &AccountInfo,
+    trader: &AccountInfo,
+    receiving: bool,
+) -> Result<bool> {
+    // Program-owned escrows such as auction PDAs only move credits under program rules
+    if owner.owner == &rwa_contract::ID {
+        return Ok(true);
+    }
+
+    // Only approve_trader creates accounts at this address, and revoke_trader closes them
+    if trader.owner == &rwa_contract::ID && !trader.data_is_empty() {
+        return Ok(true);
+    }
+
+    // Non-compliant industries may still buy credits to cover their shortfall, but not sell
+    Ok(load_industry(industry)?.is_some_and(|industry| {
+        industry.is_kyc_verified
+            && industry.is_active
+            && (industry.compliance_status == ComplianceStatus::Compliant
+                || (receiving && industry.compliance_status == ComplianceStatus::NonCompliant))
+    }))
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Transfer hook account list for the mint, written in initialize_extra_account_meta_list
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(EXTRA_ACCOUNT_METAS_COUNT).unwrap(),
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"carbon_token", mint.key().as_ref()],
+        seeds::program = rwa_contract::ID,
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Transfer authority, already validated by the token program
+    pub authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Extra account meta list written by initialize_extra_account_meta_list
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    
+    pub rwa_program: Program<'info, RwaContract>,
+    
+    /// CHECK: Industry PDA of the source owner, empty if the owner was never onboarded
+    #[account(
+        seeds = [b"industry", source_token.owner.as_ref()],
+        seeds::program = rwa_contract::ID,
+        bump
+    )]
+    pub source_industry: UncheckedAccount<'info>,
+    
+    /// CHECK: Industry PDA of the destination owner, empty if the owner was never onboarded
+    #[account(
+        seeds = [b"industry", destination_token.owner.as_ref()],
+        seeds::program = rwa_contract::ID,
+        bump
+    )]
+    pub destination_industry: UncheckedAccount<'info>,
+    
+    /// CHECK: Owner of the source token account
+    #[account(address = source_token.owner)]
+    pub source_owner: UncheckedAccount<'info>,
+    
+    /// CHECK: Owner of the destination token account
+    #[account(address = destination_token.owner)]
+    pub destination_owner: UncheckedAccount<'info>,
+    
+    /// CHECK: ApprovedTrader PDA of the source owner, empty unless approve_trader was called
+    #[account(
+        seeds = [b"approved_trader", source_token.owner.as_ref()],
+        seeds::program = rwa_contract::ID,
+        bump
+    )]
+    pub source_trader: UncheckedAccount<'info>,
+    
+    /// CHECK: ApprovedTrader PDA of the destination owner, empty unless approve_trader was called
+    #[account(
+        seeds = [b"approved_trader", destination_token.owner.as_ref()],
+        seeds::program = rwa_contract::ID,
+        bump
+    )]
+    pub destination_trader: UncheckedAccount<'info>,
+}
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::errors::ErrorCode;
+use crate::events::{
+    UserRemovedFromRole, RolePermissionsUpdated, RoleClosed, AdminTransferProposed, AdminTransferred,
+    ProgramPaused, ProgramUnpaused, AcceptedVintagesUpdated, BondConfigUpdated,
+    SlashingConfigUpdated,
+};
 
 pub fn initialize_access_control(
     ctx: Context<InitializeAccessControl>,
@@ -9,21 +15,188 @@ pub fn initialize_access_control(
     let controller = &mut ctx.accounts.controller;
     controller.default_admin = default_admin;
     controller.bump = ctx.bumps.controller;
+    controller.pending_admin = Pubkey::default();
+    controller.paused = 0;
+    controller.min_accepted_vintage = 0;
+    controller.max_accepted_vintage = 0;
+    controller.bond_mint = Pubkey::default();
+    controller.bond_lockup_seconds = 0;
+    controller.treasury = Pubkey::default();
+    Ok(())
+}
+
+pub fn migrate_controller(ctx: Context<MigrateController>) -> Result<()> {
+    let controller = ctx.accounts.controller.to_account_info();
+
+    // Older layouts share the leading fields, so only default_admin is read before resizing
+    {
+        let data = controller.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == *Controller::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        let default_admin = Pubkey::new_from_array(data[8..40].try_into().unwrap());
+        require!(default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    }
+
+    if controller.data_len() < Controller::LEN {
+        let rent_due = Rent::get()?
+            .minimum_balance(Controller::LEN)
+            .saturating_sub(controller.lamports());
+
+        if rent_due > 0 {
+            let transfer_accounts = Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: controller.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_accounts,
+            );
+
+            transfer(cpi_ctx, rent_due)?;
+        }
+
+        // Fields are fixed-width, so appended ones start past the old length and
+        // zero-extend to their unset values
+        controller.resize(Controller::LEN)?;
+    }
+
+    Ok(())
+}
+
+pub fn propose_admin(
+    ctx: Context<ProposeAdmin>,
+    new_admin: Pubkey,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+    let controller = &mut ctx.accounts.controller;
+    controller.pending_admin = new_admin;
+
+    emit!(AdminTransferProposed {
+        current_admin: controller.default_admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let controller = &mut ctx.accounts.controller;
+    require!(
+        controller.pending_admin != Pubkey::default() && controller.pending_admin == ctx.accounts.new_admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let previous_admin = controller.default_admin;
+    controller.default_admin = ctx.accounts.new_admin.key();
+    controller.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferred {
+        previous_admin,
+        new_admin: controller.default_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn set_accepted_vintages(
+    ctx: Context<SetAcceptedVintages>,
+    min_accepted_vintage: Option<u16>,
+    max_accepted_vintage: Option<u16>,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    // Zero is stored as "no bound", so it cannot also be a real vintage year
+    require!(
+        min_accepted_vintage != Some(0) && max_accepted_vintage != Some(0),
+        ErrorCode::InvalidVintageWindow
+    );
+    if let (Some(min), Some(max)) = (min_accepted_vintage, max_accepted_vintage) {
+        require!(min <= max, ErrorCode::InvalidVintageWindow);
+    }
+
+    let controller = &mut ctx.accounts.controller;
+    controller.min_accepted_vintage = min_accepted_vintage.unwrap_or(0);
+    controller.max_accepted_vintage = max_accepted_vintage.unwrap_or(0);
+
+    emit!(AcceptedVintagesUpdated {
+        min_accepted_vintage,
+        max_accepted_vintage,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Existing industries keep the mint recorded at onboarding, so changing it only affects new bonds;
+// slashing is priced per mint, so a new mint also needs its own set_slashing_config
+pub fn set_bond_config(
+    ctx: Context<SetBondConfig>,
+    bond_mint: Option<Pubkey>,
+    bond_lockup_seconds: i64,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    require!(bond_lockup_seconds >= 0, ErrorCode::InvalidBondConfig);
+
+    let controller = &mut ctx.accounts.controller;
+    controller.bond_mint = bond_mint.unwrap_or_default();
+    controller.bond_lockup_seconds = bond_lockup_seconds;
+
+    emit!(BondConfigUpdated {
+        bond_mint,
+        bond_lockup_seconds,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Prices the penalty for bonds held in one denomination; None sets the SOL penalty in lamports
+pub fn set_slashing_config(
+    ctx: Context<SetSlashingConfig>,
+    bond_mint: Option<Pubkey>,
+    penalty_per_tonne: u64,
+    treasury: Pubkey,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+    ctx.accounts.controller.treasury = treasury;
+
+    let bond_penalty = &mut ctx.accounts.bond_penalty;
+    bond_penalty.bond_mint = bond_mint.unwrap_or_default();
+    bond_penalty.penalty_per_tonne = penalty_per_tonne;
+    bond_penalty.bump = ctx.bumps.bond_penalty;
+
+    emit!(SlashingConfigUpdated {
+        bond_mint,
+        penalty_per_tonne,
+        treasury,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
 pub fn create_role(
     ctx: Context<CreateRole>,
     role_name: String,
-    actions: Vec<String>,
+    permissions: u32,
 ) -> Result<()> {
     require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    let permissions = Permission::from_bits(permissions).ok_or(ErrorCode::InvalidPermissions)?;
     
     let user_role = &mut ctx.accounts.user_role;
     user_role.controller = ctx.accounts.controller.key();
     user_role.role_name = role_name;
-    user_role.actions = actions;
-    user_role.users = Vec::new();
+    user_role.permissions = permissions;
+    user_role.member_count = 0;
     user_role.bump = ctx.bumps.user_role;
     Ok(())
 }
@@ -35,15 +208,132 @@ pub fn assign_user_to_role(
     require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
     
     let user_role = &mut ctx.accounts.user_role;
-    if !user_role.users.contains(&user) {
-        user_role.users.push(user);
-    }
+    user_role.member_count = user_role.member_count.checked_add(1).unwrap();
+
+    let role_membership = &mut ctx.accounts.role_membership;
+    role_membership.role = user_role.key();
+    role_membership.user = user;
+    role_membership.assigned_at = Clock::get()?.unix_timestamp;
+    role_membership.bump = ctx.bumps.role_membership;
+    Ok(())
+}
+
+pub fn remove_user_from_role(
+    ctx: Context<RemoveUserFromRole>,
+    user: Pubkey,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+    let user_role = &mut ctx.accounts.user_role;
+    user_role.member_count = user_role.member_count.checked_sub(1).unwrap();
+
+    emit!(UserRemovedFromRole {
+        role: user_role.key(),
+        role_name: user_role.role_name.clone(),
+        user,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
-pub fn has_role(user_role_account: &Account<UserRole>, user: &Pubkey, required_action: &str) -> bool {
-    user_role_account.actions.contains(&required_action.to_string()) && 
-    user_role_account.users.contains(user)
+pub fn update_role_permissions(
+    ctx: Context<UpdateRolePermissions>,
+    permissions: u32,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    let permissions = Permission::from_bits(permissions).ok_or(ErrorCode::InvalidPermissions)?;
+
+    let user_role = &mut ctx.accounts.user_role;
+    user_role.permissions = permissions;
+
+    emit!(RolePermissionsUpdated {
+        role: user_role.key(),
+        role_name: user_role.role_name.clone(),
+        permissions: permissions.bits(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn close_role(ctx: Context<CloseRole>) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    // Memberships are keyed by role name, so they must be revoked before the role can be recreated
+    require!(ctx.accounts.user_role.member_count == 0, ErrorCode::RoleHasMembers);
+
+    emit!(RoleClosed {
+        role: ctx.accounts.user_role.key(),
+        role_name: ctx.accounts.user_role.role_name.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn pause(
+    ctx: Context<SetPause>,
+    scope: PauseScope,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.pauser_role,
+            &ctx.accounts.pauser_membership,
+            &ctx.accounts.authority.key(),
+            Permission::PAUSE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let controller = &mut ctx.accounts.controller;
+    controller.paused |= scope.bit();
+
+    emit!(ProgramPaused {
+        scope,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn unpause(
+    ctx: Context<SetPause>,
+    scope: PauseScope,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.pauser_role,
+            &ctx.accounts.pauser_membership,
+            &ctx.accounts.authority.key(),
+            Permission::PAUSE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let controller = &mut ctx.accounts.controller;
+    controller.paused &= !scope.bit();
+
+    emit!(ProgramUnpaused {
+        scope,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Role names are free-form labels; the seeds only prove the role is one of ours, and access
+// is decided by the permission it carries
+pub fn has_role(
+    user_role_account: &Account<UserRole>,
+    role_membership: &Account<RoleMembership>,
+    user: &Pubkey,
+    required_permission: Permission,
+) -> bool {
+    user_role_account.permissions.contains(required_permission) &&
+    role_membership.role == user_role_account.key() &&
+    role_membership.user == *user
 }
 
 #[derive(Accounts)]
@@ -51,12 +341,110 @@ pub struct InitializeAccessControl<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 1,
+        space = Controller::LEN,
         seeds = [b"controller"],
         bump
     )]
     pub controller: Account<'info, Controller>,
     
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized)]
+    pub program: Program<'info, crate::program::RwaContract>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(payer.key()) @ ErrorCode::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateController<'info> {
+    /// CHECK: May still hold an older Controller layout, so it is resized before being deserialized
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump,
+        owner = crate::ID
+    )]
+    pub controller: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptedVintages<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBondConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bond_mint: Option<Pubkey>)]
+pub struct SetSlashingConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 8 + 1,
+        seeds = [b"bond_penalty", bond_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub bond_penalty: Account<'info, BondPenalty>,
+    
+    pub admin: Signer<'info>,
+    
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -74,7 +462,7 @@ pub struct CreateRole<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 4 + role_name.len() + 4 + (32 * 10) + 4 + (32 * 100) + 1,
+        space = 8 + 32 + 4 + role_name.len() + 4 + 4 + 1,
         seeds = [b"user_role", role_name.as_bytes()],
         bump
     )]
@@ -88,6 +476,7 @@ pub struct CreateRole<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(user: Pubkey)]
 pub struct AssignUserToRole<'info> {
     #[account(
         seeds = [b"controller"],
@@ -102,5 +491,109 @@ pub struct AssignUserToRole<'info> {
     )]
     pub user_role: Account<'info, UserRole>,
     
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"role_membership", user_role.role_name.as_bytes(), user.as_ref()],
+        bump
+    )]
+    pub role_membership: Account<'info, RoleMembership>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RemoveUserFromRole<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_role", user_role.role_name.as_bytes()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"role_membership", user_role.role_name.as_bytes(), user.as_ref()],
+        bump = role_membership.bump,
+        constraint = role_membership.role == user_role.key() @ ErrorCode::UserNotInRole
+    )]
+    pub role_membership: Account<'info, RoleMembership>,
+    
+    #[account(mut)]
     pub admin: Signer<'info>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateRolePermissions<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_role", user_role.role_name.as_bytes()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRole<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"user_role", user_role.role_name.as_bytes()],
+        bump = user_role.bump
+    )]
+    pub user_role: Account<'info, UserRole>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        seeds = [b"user_role", pauser_role.role_name.as_bytes()],
+        bump = pauser_role.bump
+    )]
+    pub pauser_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", pauser_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = pauser_membership.bump
+    )]
+    pub pauser_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+}
@@ -1,24 +1,44 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account, TokenAccount, Mint,
+};
+
 use crate::state::*;
 use crate::errors::ErrorCode;
-use crate::events::{DutchAuctionCreated, BidPlaced};
+use crate::events::{DutchAuctionCreated, BidPlaced, AuctionFinalized};
 use crate::access_control::has_role;
+use crate::industry::is_frozen;
+use crate::transfer_hook::transfer_checked_with_hook;
 
-pub fn create_dutch_auction(
-    ctx: Context<CreateDutchAuction>,
+pub fn create_dutch_auction<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateDutchAuction<'info>>,
     start_price: u64,
     end_price: u64,
     duration_seconds: i64,
     tokens_for_sale: u64,
 ) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Auctions), ErrorCode::ModulePaused);
     // Check if user has AUCTION_AUTHORITY role
     require!(
-        has_role(&ctx.accounts.auction_authority_role, &ctx.accounts.authority.key(), "AUCTION_AUTHORITY"),
+        has_role(
+            &ctx.accounts.auction_authority_role,
+            &ctx.accounts.auction_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::AUCTION,
+        ),
         ErrorCode::InsufficientPermissions
     );
+    require!(
+        tokens_for_sale > 0 && duration_seconds > 0 && start_price >= end_price,
+        ErrorCode::InvalidAuctionParameters
+    );
+    require!(!is_frozen(&ctx.accounts.seller_industry)?, ErrorCode::IndustryFrozen);
 
-    let auction = &mut ctx.accounts.auction;
     let current_time = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.carbon_token.is_expired(current_time), ErrorCode::CarbonTokenExpired);
+
+    let auction = &mut ctx.accounts.auction;
 
     auction.seller = ctx.accounts.seller.key();
     auction.token_mint = ctx.accounts.token_mint.key();
@@ -28,8 +48,26 @@ pub fn create_dutch_auction(
     auction.end_time = current_time + duration_seconds;
     auction.tokens_for_sale = tokens_for_sale;
     auction.tokens_sold = 0;
+    auction.quote_mint = ctx.accounts.quote_mint.as_ref().map(|mint| mint.key());
     auction.is_active = true;
     auction.bump = ctx.bumps.auction;
+    auction.vault_bump = ctx.bumps.vault;
+
+    // Escrow the credits for sale in the auction vault
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.seller_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, tokens_for_sale, ctx.accounts.token_mint.decimals)?;
 
     emit!(DutchAuctionCreated {
         auction: auction.key(),
@@ -38,21 +76,27 @@ pub fn create_dutch_auction(
         end_price,
         duration_seconds,
         tokens_for_sale,
+        quote_mint: auction.quote_mint,
         timestamp: current_time,
     });
 
     Ok(())
 }
 
-pub fn place_bid(
-    ctx: Context<PlaceBid>,
+pub fn place_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
     token_amount: u64,
 ) -> Result<()> {
-    let auction = &mut ctx.accounts.auction;
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Auctions), ErrorCode::ModulePaused);
+    let auction = &ctx.accounts.auction;
     let current_time = Clock::get()?.unix_timestamp;
 
+    require!(token_amount > 0, ErrorCode::InvalidAmount);
+    require!(!is_frozen(&ctx.accounts.bidder_industry)?, ErrorCode::IndustryFrozen);
+    require!(!is_frozen(&ctx.accounts.seller_industry)?, ErrorCode::IndustryFrozen);
     require!(auction.is_active, ErrorCode::AuctionNotActive);
     require!(current_time <= auction.end_time, ErrorCode::AuctionExpired);
+    require!(!ctx.accounts.carbon_token.is_expired(current_time), ErrorCode::CarbonTokenExpired);
     require!(token_amount <= auction.tokens_for_sale - auction.tokens_sold, ErrorCode::InsufficientTokensAvailable);
 
     let current_price = calculate_dutch_auction_price(
@@ -65,6 +109,81 @@ pub fn place_bid(
 
     let total_cost = current_price.checked_mul(token_amount).unwrap();
 
+    // Pay the seller in the auction's quote currency
+    match auction.quote_mint {
+        Some(quote_mint) => {
+            let (
+                Some(quote_mint_account),
+                Some(bidder_quote_account),
+                Some(seller_quote_account),
+                Some(quote_token_program),
+            ) = (
+                ctx.accounts.quote_mint.as_ref(),
+                ctx.accounts.bidder_quote_account.as_ref(),
+                ctx.accounts.seller_quote_account.as_ref(),
+                ctx.accounts.quote_token_program.as_ref(),
+            ) else {
+                return err!(ErrorCode::QuoteAccountsRequired);
+            };
+
+            require_keys_eq!(quote_mint_account.key(), quote_mint, ErrorCode::InvalidMint);
+            require_keys_eq!(seller_quote_account.mint, quote_mint, ErrorCode::InvalidMint);
+            require_keys_eq!(seller_quote_account.owner, auction.seller, ErrorCode::Unauthorized);
+
+            let transfer_accounts = TransferChecked {
+                from: bidder_quote_account.to_account_info(),
+                mint: quote_mint_account.to_account_info(),
+                to: seller_quote_account.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                quote_token_program.to_account_info(),
+                transfer_accounts,
+            );
+
+            transfer_checked(cpi_ctx, total_cost, quote_mint_account.decimals)?;
+        }
+        None => {
+            let transfer_accounts = Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_accounts,
+            );
+
+            transfer(cpi_ctx, total_cost)?;
+        }
+    }
+
+    // Release the purchased credits from the vault to the bidder
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"dutch_auction",
+        auction.seller.as_ref(),
+        auction.token_mint.as_ref(),
+        &[auction.bump],
+    ]];
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.bidder_token_account.to_account_info(),
+        authority: auction.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        signer_seeds,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, token_amount, ctx.accounts.token_mint.decimals)?;
+
+    let auction = &mut ctx.accounts.auction;
     auction.tokens_sold = auction.tokens_sold.checked_add(token_amount).unwrap();
 
     if auction.tokens_sold >= auction.tokens_for_sale {
@@ -83,6 +202,129 @@ pub fn place_bid(
     Ok(())
 }
 
+pub fn cancel_auction<'info>(ctx: Context<'_, '_, '_, 'info, CancelAuction<'info>>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == ctx.accounts.auction.seller
+            || ctx
+                .accounts
+                .auction_authority_role
+                .as_ref()
+                .zip(ctx.accounts.auction_authority_membership.as_ref())
+                .is_some_and(|(role, membership)| has_role(role, membership, &authority, Permission::AUCTION)),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(ctx.accounts.auction.is_active, ErrorCode::AuctionNotActive);
+    require!(!is_frozen(&ctx.accounts.seller_industry)?, ErrorCode::IndustryFrozen);
+
+    // Bids settle atomically, so only the unsold remainder is left to return
+    let tokens_returned = return_unsold_credits(
+        &ctx.accounts.auction,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.seller,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(AuctionFinalized {
+        auction: ctx.accounts.auction.key(),
+        seller: ctx.accounts.auction.seller,
+        tokens_sold: ctx.accounts.auction.tokens_sold,
+        tokens_returned,
+        cancelled: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn finalize_auction<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeAuction<'info>>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        current_time > auction.end_time || auction.tokens_sold >= auction.tokens_for_sale,
+        ErrorCode::AuctionNotEnded
+    );
+    require!(!is_frozen(&ctx.accounts.seller_industry)?, ErrorCode::IndustryFrozen);
+
+    let tokens_returned = return_unsold_credits(
+        auction,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.seller,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(AuctionFinalized {
+        auction: auction.key(),
+        seller: auction.seller,
+        tokens_sold: auction.tokens_sold,
+        tokens_returned,
+        cancelled: false,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+fn return_unsold_credits<'info>(
+    auction: &Account<'info, DutchAuction>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    seller_token_account: &InterfaceAccount<'info, TokenAccount>,
+    seller: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"dutch_auction",
+        auction.seller.as_ref(),
+        auction.token_mint.as_ref(),
+        &[auction.bump],
+    ]];
+
+    let tokens_returned = vault.amount;
+
+    if tokens_returned > 0 {
+        let transfer_accounts = TransferChecked {
+            from: vault.to_account_info(),
+            mint: token_mint.to_account_info(),
+            to: seller_token_account.to_account_info(),
+            authority: auction.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, tokens_returned, token_mint.decimals)?;
+    }
+
+    let close_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: seller.to_account_info(),
+        authority: auction.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        close_accounts,
+        signer_seeds,
+    );
+
+    close_account(cpi_ctx)?;
+
+    Ok(tokens_returned)
+}
+
 pub fn calculate_dutch_auction_price(
     start_price: u64,
     end_price: u64,
@@ -103,36 +345,88 @@ pub fn calculate_dutch_auction_price(
 
 #[derive(Accounts)]
 pub struct CreateDutchAuction<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + (1 + 32) + 1 + 1 + 1,
         seeds = [b"dutch_auction", seller.key().as_ref(), token_mint.key().as_ref()],
         bump
     )]
     pub auction: Account<'info, DutchAuction>,
     
     #[account(
-        seeds = [b"user_role", b"AUCTION_AUTHORITY"],
+        init,
+        payer = payer,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = auction,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"user_role", auction_authority_role.role_name.as_bytes()],
         bump = auction_authority_role.bump
     )]
     pub auction_authority_role: Account<'info, UserRole>,
     
-    /// CHECK: This is the seller of the auction
-    pub seller: AccountInfo<'info>,
+    #[account(
+        seeds = [b"role_membership", auction_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = auction_authority_membership.bump
+    )]
+    pub auction_authority_membership: Account<'info, RoleMembership>,
     
-    /// CHECK: This is the token mint being sold
-    pub token_mint: AccountInfo<'info>,
+    pub seller: Signer<'info>,
+    
+    /// CHECK: Industry PDA of the seller, empty if the seller was never onboarded
+    #[account(
+        seeds = [b"industry", seller.key().as_ref()],
+        bump
+    )]
+    pub seller_industry: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"carbon_token", token_mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Account<'info, CarbonToken>,
+    
+    /// Optional SPL mint bids are paid in; SOL is used when omitted
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
     
     pub authority: Signer<'info>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct PlaceBid<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
     #[account(
         mut,
         seeds = [b"dutch_auction", auction.seller.as_ref(), auction.token_mint.as_ref()],
@@ -140,5 +434,155 @@ pub struct PlaceBid<'info> {
     )]
     pub auction: Account<'info, DutchAuction>,
     
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = auction.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [b"carbon_token", token_mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Account<'info, CarbonToken>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = bidder,
+        token::token_program = token_program,
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Industry PDA of the bidder, empty if the bidder was never onboarded
+    #[account(
+        seeds = [b"industry", bidder.key().as_ref()],
+        bump
+    )]
+    pub bidder_industry: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives SOL payment, must match the auction seller
+    #[account(mut, address = auction.seller @ ErrorCode::Unauthorized)]
+    pub seller: AccountInfo<'info>,
+    
+    /// CHECK: Industry PDA of the seller, empty if the seller was never onboarded
+    #[account(
+        seeds = [b"industry", auction.seller.as_ref()],
+        bump
+    )]
+    pub seller_industry: UncheckedAccount<'info>,
+    
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub bidder_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub seller_quote_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+    
+    #[account(mut)]
     pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"dutch_auction", auction.seller.as_ref(), auction.token_mint.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = auction.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Receives the unsold credits and reclaimed rent, must match the auction seller
+    #[account(mut, address = auction.seller @ ErrorCode::Unauthorized)]
+    pub seller: AccountInfo<'info>,
+    
+    /// CHECK: Industry PDA of the seller, empty if the seller was never onboarded
+    #[account(
+        seeds = [b"industry", auction.seller.as_ref()],
+        bump
+    )]
+    pub seller_industry: UncheckedAccount<'info>,
+    
+    // The role accounts are only needed when someone other than the seller cancels
+    #[account(
+        seeds = [b"user_role", auction_authority_role.role_name.as_bytes()],
+        bump = auction_authority_role.bump
+    )]
+    pub auction_authority_role: Option<Account<'info, UserRole>>,
+    
+    // has_role ties the membership to the role and the signer, so no seeds are needed here
+    pub auction_authority_membership: Option<Account<'info, RoleMembership>>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeAuction<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"dutch_auction", auction.seller.as_ref(), auction.token_mint.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = auction.token_mint @ ErrorCode::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Receives the unsold credits and reclaimed rent, must match the auction seller
+    #[account(mut, address = auction.seller @ ErrorCode::Unauthorized)]
+    pub seller: AccountInfo<'info>,
+    
+    /// CHECK: Industry PDA of the seller, empty if the seller was never onboarded
+    #[account(
+        seeds = [b"industry", auction.seller.as_ref()],
+        bump
+    )]
+    pub seller_industry: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
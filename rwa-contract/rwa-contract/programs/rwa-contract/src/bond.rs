@@ -0,0 +1,378 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{TokenInterface, TransferChecked, transfer_checked, TokenAccount, Mint};
+
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::{BondDeposited, BondWithdrawn, BondSlashed};
+use crate::access_control::has_role;
+
+pub fn top_up_bond(ctx: Context<TopUpBond>, amount: u64) -> Result<()> {
+    let industry = &ctx.accounts.industry;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(industry.is_active, ErrorCode::IndustryNotActive);
+    require!(industry.compliance_status != ComplianceStatus::Frozen, ErrorCode::IndustryFrozen);
+
+    deposit_bond(
+        industry.bond_mint,
+        amount,
+        &ctx.accounts.industry_authority,
+        &ctx.accounts.bond_vault.to_account_info(),
+        &ctx.accounts.system_program,
+        BondTokenAccounts {
+            mint: ctx.accounts.bond_mint.as_deref(),
+            from: ctx.accounts.industry_bond_account.as_ref().map(|account| account.to_account_info()),
+            to: ctx.accounts.vault_bond_account.as_ref().map(|account| account.to_account_info()),
+            token_program: ctx.accounts.token_program.as_ref(),
+        },
+    )?;
+
+    let industry = &mut ctx.accounts.industry;
+    industry.bond_amount = industry.bond_amount.checked_add(amount).unwrap();
+
+    emit!(BondDeposited {
+        industry: industry.authority,
+        bond_mint: industry.bond_mint,
+        amount,
+        total_bond: industry.bond_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+    let industry = &ctx.accounts.industry;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(industry.compliance_status == ComplianceStatus::Compliant, ErrorCode::IndustryNotCompliant);
+    require!(Clock::get()?.unix_timestamp >= industry.bond_locked_until, ErrorCode::BondLocked);
+    require!(amount <= industry.bond_amount, ErrorCode::InsufficientBond);
+
+    pay_out_bond(
+        industry,
+        amount,
+        &ctx.accounts.bond_vault,
+        &ctx.accounts.industry_authority.to_account_info(),
+        BondTokenAccounts {
+            mint: ctx.accounts.bond_mint.as_deref(),
+            from: ctx.accounts.vault_bond_account.as_ref().map(|account| account.to_account_info()),
+            to: ctx.accounts.industry_bond_account.as_ref().map(|account| account.to_account_info()),
+            token_program: ctx.accounts.token_program.as_ref(),
+        },
+    )?;
+
+    let industry = &mut ctx.accounts.industry;
+    industry.bond_amount = industry.bond_amount.checked_sub(amount).unwrap();
+
+    emit!(BondWithdrawn {
+        industry: industry.authority,
+        bond_mint: industry.bond_mint,
+        amount,
+        total_bond: industry.bond_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.compliance_authority_role,
+            &ctx.accounts.compliance_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::COMPLIANCE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let penalty_per_tonne = ctx.accounts.bond_penalty.penalty_per_tonne;
+    require!(penalty_per_tonne > 0, ErrorCode::SlashingNotConfigured);
+
+    // Only whole tonnes the bond can cover are slashed; the rest stay open for a later slash
+    let industry = &ctx.accounts.industry;
+    let tonnes = industry
+        .unpenalized_tonnes()
+        .min(industry.bond_amount / penalty_per_tonne);
+    require!(tonnes > 0, ErrorCode::NothingToSlash);
+    let penalty = tonnes.checked_mul(penalty_per_tonne).unwrap();
+
+    pay_out_bond(
+        industry,
+        penalty,
+        &ctx.accounts.bond_vault,
+        &ctx.accounts.treasury.to_account_info(),
+        BondTokenAccounts {
+            mint: ctx.accounts.bond_mint.as_deref(),
+            from: ctx.accounts.vault_bond_account.as_ref().map(|account| account.to_account_info()),
+            to: ctx.accounts.treasury_bond_account.as_ref().map(|account| account.to_account_info()),
+            token_program: ctx.accounts.token_program.as_ref(),
+        },
+    )?;
+
+    let industry = &mut ctx.accounts.industry;
+    industry.bond_amount = industry.bond_amount.checked_sub(penalty).unwrap();
+    industry.slashed_tonnes = industry.slashed_tonnes.checked_add(tonnes).unwrap();
+    industry.penalties_paid = industry.penalties_paid.checked_add(penalty).unwrap();
+
+    // Once every uncovered tonne has been paid for the industry is back in good standing
+    if industry.unpenalized_tonnes() == 0 && industry.compliance_status != ComplianceStatus::Frozen {
+        industry.compliance_status = ComplianceStatus::Compliant;
+    }
+
+    emit!(BondSlashed {
+        industry: industry.authority,
+        bond_mint: industry.bond_mint,
+        tonnes,
+        penalty_per_tonne,
+        penalty,
+        total_bond: industry.bond_amount,
+        treasury: ctx.accounts.treasury.key(),
+        compliance_status: industry.compliance_status.clone(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Token-side accounts for a mint-denominated bond; all of them are None for SOL bonds
+pub struct BondTokenAccounts<'a, 'info> {
+    pub mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub from: Option<AccountInfo<'info>>,
+    pub to: Option<AccountInfo<'info>>,
+    pub token_program: Option<&'a Interface<'info, TokenInterface>>,
+}
+
+// Moves a bond into the vault, in SOL or in the mint the industry was onboarded with
+pub fn deposit_bond<'info>(
+    bond_mint: Option<Pubkey>,
+    amount: u64,
+    depositor: &Signer<'info>,
+    bond_vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_accounts: BondTokenAccounts<'_, 'info>,
+) -> Result<()> {
+    match bond_mint {
+        Some(bond_mint) => {
+            let BondTokenAccounts {
+                mint: Some(mint),
+                from: Some(depositor_bond_account),
+                to: Some(vault_bond_account),
+                token_program: Some(token_program),
+            } = token_accounts
+            else {
+                return err!(ErrorCode::BondAccountsRequired);
+            };
+            require_keys_eq!(mint.key(), bond_mint, ErrorCode::InvalidMint);
+
+            let transfer_accounts = TransferChecked {
+                from: depositor_bond_account,
+                mint: mint.to_account_info(),
+                to: vault_bond_account,
+                authority: depositor.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                token_program.to_account_info(),
+                transfer_accounts,
+            );
+
+            transfer_checked(cpi_ctx, amount, mint.decimals)
+        }
+        None => {
+            let transfer_accounts = Transfer {
+                from: depositor.to_account_info(),
+                to: bond_vault.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                system_program.to_account_info(),
+                transfer_accounts,
+            );
+
+            transfer(cpi_ctx, amount)
+        }
+    }
+}
+
+// Pays out of the vault to a recipient; SOL comes straight off the program-owned vault's lamports
+pub fn pay_out_bond<'info>(
+    industry: &Account<'info, Industry>,
+    amount: u64,
+    bond_vault: &Account<'info, BondVault>,
+    recipient: &AccountInfo<'info>,
+    token_accounts: BondTokenAccounts<'_, 'info>,
+) -> Result<()> {
+    match industry.bond_mint {
+        Some(bond_mint) => {
+            let BondTokenAccounts {
+                mint: Some(mint),
+                from: Some(vault_bond_account),
+                to: Some(recipient_bond_account),
+                token_program: Some(token_program),
+            } = token_accounts
+            else {
+                return err!(ErrorCode::BondAccountsRequired);
+            };
+            require_keys_eq!(mint.key(), bond_mint, ErrorCode::InvalidMint);
+
+            let industry_key = industry.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"bond_vault",
+                industry_key.as_ref(),
+                &[bond_vault.bump],
+            ]];
+
+            let transfer_accounts = TransferChecked {
+                from: vault_bond_account,
+                mint: mint.to_account_info(),
+                to: recipient_bond_account,
+                authority: bond_vault.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                transfer_accounts,
+                signer_seeds,
+            );
+
+            transfer_checked(cpi_ctx, amount, mint.decimals)
+        }
+        None => {
+            bond_vault.sub_lamports(amount)?;
+            recipient.add_lamports(amount)?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct TopUpBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"industry", industry_authority.key().as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", industry.key().as_ref()],
+        bump = bond_vault.bump
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    pub bond_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub industry_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::authority = bond_vault,
+    )]
+    pub vault_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub industry_authority: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"industry", industry_authority.key().as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", industry.key().as_ref()],
+        bump = bond_vault.bump
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    pub bond_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::authority = bond_vault,
+    )]
+    pub vault_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::authority = industry_authority,
+    )]
+    pub industry_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub industry_authority: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry", industry.authority.as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", industry.key().as_ref()],
+        bump = bond_vault.bump
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    #[account(
+        seeds = [b"bond_penalty", industry.bond_mint.unwrap_or_default().as_ref()],
+        bump = bond_penalty.bump
+    )]
+    pub bond_penalty: Account<'info, BondPenalty>,
+    
+    /// CHECK: Receives SOL penalties, must match the configured treasury
+    #[account(mut, address = controller.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub bond_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::authority = bond_vault,
+    )]
+    pub vault_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::authority = treasury,
+    )]
+    pub treasury_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        seeds = [b"user_role", compliance_authority_role.role_name.as_bytes()],
+        bump = compliance_authority_role.bump
+    )]
+    pub compliance_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", compliance_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = compliance_authority_membership.bump
+    )]
+    pub compliance_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
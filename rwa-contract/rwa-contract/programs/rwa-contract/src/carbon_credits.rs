@@ -1,39 +1,174 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{TokenInterface, MintTo, mint_to, TokenAccount, Mint};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{
+    TokenInterface, MintTo, mint_to, Burn, burn, TransferChecked, TokenAccount, Mint,
+    FreezeAccount, freeze_account, ThawAccount, thaw_account,
+    TokenMetadataInitialize, token_metadata_initialize, TokenMetadataUpdateField, token_metadata_update_field,
+};
+use anchor_spl::token_interface::spl_token_metadata_interface::state::Field;
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::state::*;
 use crate::errors::ErrorCode;
-use crate::events::CarbonCreditsMinted;
+use crate::events::{
+    CarbonCreditsMinted, CreditsRetired, CarbonTokenExpired, IssuanceIncreased, CarbonTokenMetadataUpdated, CreditsClawedBack,
+};
 use crate::access_control::has_role;
+use crate::pda::create_pda_account;
+use crate::transfer_hook::{transfer_checked_with_hook, TRANSFER_HOOK_PROGRAM_ID};
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_carbon_token(
     ctx: Context<InitializeCarbonToken>,
     name: String,
     symbol: String,
     uri: String,
     co2_tonnes: u64,
-    project_id: String,
+    buffer_bps: u16,
     expiry_date: i64,
+    vintage: u16,
     issuer_name: String,
 ) -> Result<()> {
+    require!(buffer_bps <= CarbonToken::MAX_BUFFER_BPS, ErrorCode::InvalidBufferBps);
     require!(
-        has_role(&ctx.accounts.mint_authority_role, &ctx.accounts.authority.key(), "MINT_AUTHORITY"),
+        has_role(
+            &ctx.accounts.mint_authority_role,
+            &ctx.accounts.mint_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::MINT,
+        ),
         ErrorCode::InsufficientPermissions
     );
 
     let carbon_token = &mut ctx.accounts.carbon_token;
     carbon_token.mint = ctx.accounts.mint.key();
+    carbon_token.project = ctx.accounts.project.key();
     carbon_token.name = name;
     carbon_token.symbol = symbol;
     carbon_token.uri = uri;
     carbon_token.co2_tonnes = co2_tonnes;
-    carbon_token.project_id = project_id;
+    carbon_token.buffer_bps = buffer_bps;
+    carbon_token.project_id = ctx.accounts.project.project_id.clone();
     carbon_token.expiry_date = expiry_date;
+    carbon_token.vintage = vintage;
     carbon_token.issuer_name = issuer_name;
     carbon_token.total_supply = 0;
+    carbon_token.total_issued = 0;
+    carbon_token.retirement_count = 0;
+    carbon_token.batch_count = 0;
     carbon_token.is_active = true;
     carbon_token.bump = ctx.bumps.carbon_token;
+    carbon_token.authority_bump = ctx.bumps.credit_authority;
+
+    let project = &mut ctx.accounts.project;
+    project.carbon_token_count = project.carbon_token_count.checked_add(1).unwrap();
+
+    // The mint is its own metadata account, so wallets read these fields straight off it
+    let carbon_token_key = ctx.accounts.carbon_token.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"credit_authority",
+        carbon_token_key.as_ref(),
+        &[ctx.bumps.credit_authority],
+    ]];
+
+    let metadata_accounts = TokenMetadataInitialize {
+        program_id: ctx.accounts.token_program.to_account_info(),
+        metadata: ctx.accounts.mint.to_account_info(),
+        update_authority: ctx.accounts.credit_authority.to_account_info(),
+        mint_authority: ctx.accounts.credit_authority.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        metadata_accounts,
+        signer_seeds,
+    );
+
+    token_metadata_initialize(
+        cpi_ctx,
+        ctx.accounts.carbon_token.name.clone(),
+        ctx.accounts.carbon_token.symbol.clone(),
+        ctx.accounts.carbon_token.uri.clone(),
+    )?;
+
+    update_metadata_fields(
+        additional_metadata(&ctx.accounts.carbon_token),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.credit_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    fund_rent_exemption(
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    Ok(())
+}
+
+pub fn update_carbon_token_metadata(
+    ctx: Context<UpdateCarbonTokenMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+    issuer_name: String,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.mint_authority_role,
+            &ctx.accounts.mint_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::MINT,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let carbon_token = &mut ctx.accounts.carbon_token;
+    carbon_token.name = name;
+    carbon_token.symbol = symbol;
+    carbon_token.uri = uri;
+    carbon_token.issuer_name = issuer_name;
+
+    let carbon_token_key = carbon_token.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"credit_authority",
+        carbon_token_key.as_ref(),
+        &[carbon_token.authority_bump],
+    ]];
+
+    // Rewrites every mirrored field, so the mint always ends up matching the carbon token
+    let mut fields = vec![
+        (Field::Name, carbon_token.name.clone()),
+        (Field::Symbol, carbon_token.symbol.clone()),
+        (Field::Uri, carbon_token.uri.clone()),
+    ];
+    fields.extend(additional_metadata(carbon_token));
+
+    update_metadata_fields(
+        fields,
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.credit_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    fund_rent_exemption(
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(CarbonTokenMetadataUpdated {
+        carbon_token: carbon_token_key,
+        mint: ctx.accounts.mint.key(),
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -41,87 +176,600 @@ pub fn initialize_carbon_token(
 pub fn mint_carbon_credits(
     ctx: Context<MintCarbonCredits>,
     amount: u64,
+    monitoring_period_start: i64,
+    monitoring_period_end: i64,
+    serial_prefix: String,
+    serial_start: u64,
+    serial_end: u64,
 ) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Minting), ErrorCode::ModulePaused);
     require!(
-        has_role(&ctx.accounts.mint_authority_role, &ctx.accounts.mint_authority.key(), "MINT_AUTHORITY"),
+        has_role(
+            &ctx.accounts.mint_authority_role,
+            &ctx.accounts.mint_authority_membership,
+            &ctx.accounts.mint_authority.key(),
+            Permission::MINT,
+        ),
         ErrorCode::InsufficientPermissions
     );
 
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(monitoring_period_start < monitoring_period_end, ErrorCode::InvalidMonitoringPeriod);
+    require!(
+        serial_prefix.len() <= SerialRegistry::MAX_PREFIX_LEN
+            && serial_start <= serial_end
+            && serial_end - serial_start == amount - 1,
+        ErrorCode::InvalidSerialBlock
+    );
+
     let carbon_token = &mut ctx.accounts.carbon_token;
     require!(carbon_token.is_active, ErrorCode::TokenNotActive);
+    require!(!carbon_token.is_expired(Clock::get()?.unix_timestamp), ErrorCode::CarbonTokenExpired);
+
+    // Capped on cumulative issuance so burned credits never free up room to mint again
+    let total_issued = carbon_token.total_issued.checked_add(amount).unwrap();
+    require!(total_issued <= carbon_token.issuance_cap(), ErrorCode::IssuanceCapExceeded);
+
+    // Registries issue non-contiguous blocks and the bridge may import them out of order,
+    // so each block is checked against every range already issued under the prefix
+    let registry_info = ctx.accounts.serial_registry.to_account_info();
+    let mut serial_registry = if registry_info.data_is_empty() {
+        let serial_registry = SerialRegistry {
+            project_id: carbon_token.project_id.clone(),
+            serial_prefix: serial_prefix.clone(),
+            issued_blocks: Vec::new(),
+            bump: ctx.bumps.serial_registry,
+        };
+        create_pda_account(
+            &registry_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            serial_registry.space(),
+            &[
+                b"serial_registry",
+                hash(carbon_token.project_id.as_bytes()).as_ref(),
+                hash(serial_prefix.as_bytes()).as_ref(),
+                &[ctx.bumps.serial_registry],
+            ],
+        )?;
+        serial_registry
+    } else {
+        require_keys_eq!(*registry_info.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+        SerialRegistry::try_deserialize(&mut &registry_info.try_borrow_data()?[..])?
+    };
+    require!(serial_registry.record_block(serial_start, serial_end), ErrorCode::SerialRangeOverlap);
 
+    let registry_space = serial_registry.space();
+    if registry_info.data_len() < registry_space {
+        registry_info.resize(registry_space)?;
+        fund_rent_exemption(&registry_info, &ctx.accounts.payer, &ctx.accounts.system_program)?;
+    }
+    serial_registry.try_serialize(&mut &mut registry_info.try_borrow_mut_data()?[..])?;
+
+    // Each issuance is recorded as its own batch carrying its registry serial block
+    let batch = &mut ctx.accounts.batch;
+    batch.carbon_token = carbon_token.key();
+    batch.mint = ctx.accounts.mint.key();
+    batch.batch_number = carbon_token.batch_count;
+    batch.vintage = carbon_token.vintage;
+    batch.monitoring_period_start = monitoring_period_start;
+    batch.monitoring_period_end = monitoring_period_end;
+    batch.serial_prefix = serial_prefix;
+    batch.serial_start = serial_start;
+    batch.serial_end = serial_end;
+    batch.quantity = amount;
+    batch.recipient = ctx.accounts.recipient.key();
+    batch.issued_at = Clock::get()?.unix_timestamp;
+    batch.bump = ctx.bumps.batch;
+
+    carbon_token.batch_count = carbon_token.batch_count.checked_add(1).unwrap();
+    carbon_token.total_issued = total_issued;
     carbon_token.total_supply = carbon_token.total_supply.checked_add(amount).unwrap();
 
+    let carbon_token_key = carbon_token.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"credit_authority",
+        carbon_token_key.as_ref(),
+        &[carbon_token.authority_bump],
+    ]];
+
     let mint_accounts = MintTo {
         mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.token_account.to_account_info(),
-        authority: ctx.accounts.mint_authority.to_account_info(),
+        authority: ctx.accounts.credit_authority.to_account_info(),
     };
 
-    let cpi_ctx = CpiContext::new(
+    let cpi_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         mint_accounts,
+        signer_seeds,
     );
 
     mint_to(cpi_ctx, amount)?;
 
+    let batch = &ctx.accounts.batch;
     emit!(CarbonCreditsMinted {
         mint: ctx.accounts.mint.key(),
         recipient: ctx.accounts.recipient.key(),
         amount,
+        batch: batch.key(),
+        vintage: batch.vintage,
+        serial_prefix: batch.serial_prefix.clone(),
+        serial_start: batch.serial_start,
+        serial_end: batch.serial_end,
+        timestamp: batch.issued_at,
+    });
+
+    Ok(())
+}
+
+pub fn increase_issuance(
+    ctx: Context<IncreaseIssuance>,
+    additional_tonnes: u64,
+    attestation_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.verifier_role,
+            &ctx.accounts.verifier_membership,
+            &ctx.accounts.verifier.key(),
+            Permission::VERIFY,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(additional_tonnes > 0, ErrorCode::InvalidAmount);
+
+    let carbon_token = &mut ctx.accounts.carbon_token;
+    require!(carbon_token.is_active, ErrorCode::TokenNotActive);
+
+    let previous_co2_tonnes = carbon_token.co2_tonnes;
+    carbon_token.co2_tonnes = previous_co2_tonnes.checked_add(additional_tonnes).unwrap();
+
+    let carbon_token_key = carbon_token.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"credit_authority",
+        carbon_token_key.as_ref(),
+        &[carbon_token.authority_bump],
+    ]];
+
+    update_metadata_fields(
+        vec![(Field::Key("co2_tonnes".to_string()), carbon_token.co2_tonnes.to_string())],
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.credit_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    fund_rent_exemption(
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(IssuanceIncreased {
+        carbon_token: carbon_token_key,
+        verifier: ctx.accounts.verifier.key(),
+        previous_co2_tonnes,
+        new_co2_tonnes: ctx.accounts.carbon_token.co2_tonnes,
+        attestation_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn retire_credits(
+    ctx: Context<RetireCredits>,
+    amount: u64,
+    beneficiary: String,
+    reason: String,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(ctx.accounts.carbon_token.is_active, ErrorCode::TokenNotActive);
+    require!(
+        !ctx.accounts.carbon_token.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::CarbonTokenExpired
+    );
+
+    let burn_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        burn_accounts,
+    );
+
+    burn(cpi_ctx, amount)?;
+
+    let carbon_token = &mut ctx.accounts.carbon_token;
+    carbon_token.total_supply = carbon_token.total_supply.checked_sub(amount).unwrap();
+
+    let serial = carbon_token.retirement_count;
+    carbon_token.retirement_count = serial.checked_add(1).unwrap();
+
+    let certificate = &mut ctx.accounts.certificate;
+    certificate.carbon_token = carbon_token.key();
+    certificate.mint = ctx.accounts.mint.key();
+    certificate.retiree = ctx.accounts.owner.key();
+    certificate.beneficiary = beneficiary;
+    certificate.reason = reason;
+    certificate.amount = amount;
+    certificate.vintage = carbon_token.vintage;
+    certificate.project_id = carbon_token.project_id.clone();
+    certificate.timestamp = Clock::get()?.unix_timestamp;
+    certificate.serial = serial;
+    certificate.bump = ctx.bumps.certificate;
+
+    emit!(CreditsRetired {
+        certificate: certificate.key(),
+        mint: certificate.mint,
+        retiree: certificate.retiree,
+        beneficiary: certificate.beneficiary.clone(),
+        reason: certificate.reason.clone(),
+        amount,
+        vintage: certificate.vintage,
+        project_id: certificate.project_id.clone(),
+        serial,
+        timestamp: certificate.timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn clawback_credits<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClawbackCredits<'info>>,
+    amount: u64,
+    reason: ClawbackReason,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.compliance_authority_role,
+            &ctx.accounts.compliance_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::COMPLIANCE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let carbon_token_key = ctx.accounts.carbon_token.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"credit_authority",
+        carbon_token_key.as_ref(),
+        &[ctx.accounts.carbon_token.authority_bump],
+    ]];
+
+    // Token-2022 refuses to move credits out of a frozen account even for the permanent delegate,
+    // so a holder frozen by freeze_industry is thawed for the clawback and frozen again after it
+    let source_frozen = ctx.accounts.source_token_account.is_frozen();
+    if source_frozen {
+        let thaw_accounts = ThawAccount {
+            account: ctx.accounts.source_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.credit_authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            thaw_accounts,
+            signer_seeds,
+        );
+
+        thaw_account(cpi_ctx)?;
+    }
+
+    // credit_authority is the mint's permanent delegate, so no holder signature is needed
+    match ctx.accounts.destination_token_account.as_ref() {
+        Some(destination_token_account) => {
+            let transfer_accounts = TransferChecked {
+                from: ctx.accounts.source_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: destination_token_account.to_account_info(),
+                authority: ctx.accounts.credit_authority.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_accounts,
+                signer_seeds,
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+            transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+        }
+        None => {
+            let burn_accounts = Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.source_token_account.to_account_info(),
+                authority: ctx.accounts.credit_authority.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                burn_accounts,
+                signer_seeds,
+            );
+
+            burn(cpi_ctx, amount)?;
+
+            let carbon_token = &mut ctx.accounts.carbon_token;
+            carbon_token.total_supply = carbon_token.total_supply.checked_sub(amount).unwrap();
+        }
+    }
+
+    if source_frozen {
+        let freeze_accounts = FreezeAccount {
+            account: ctx.accounts.source_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.credit_authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            freeze_accounts,
+            signer_seeds,
+        );
+
+        freeze_account(cpi_ctx)?;
+    }
+
+    emit!(CreditsClawedBack {
+        carbon_token: carbon_token_key,
+        mint: ctx.accounts.mint.key(),
+        source: ctx.accounts.source_token_account.key(),
+        destination: ctx.accounts.destination_token_account.as_ref().map(|account| account.key()),
+        amount,
+        reason,
+        authority: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
 
+pub fn expire_carbon_token(ctx: Context<ExpireCarbonToken>) -> Result<()> {
+    let carbon_token = &mut ctx.accounts.carbon_token;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(carbon_token.is_active, ErrorCode::TokenNotActive);
+    require!(carbon_token.is_expired(current_time), ErrorCode::CarbonTokenNotExpired);
+
+    carbon_token.is_active = false;
+
+    emit!(CarbonTokenExpired {
+        carbon_token: carbon_token.key(),
+        mint: carbon_token.mint,
+        expiry_date: carbon_token.expiry_date,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+fn additional_metadata(carbon_token: &CarbonToken) -> Vec<(Field, String)> {
+    vec![
+        (Field::Key("project_id".to_string()), carbon_token.project_id.clone()),
+        (Field::Key("issuer".to_string()), carbon_token.issuer_name.clone()),
+        (Field::Key("vintage".to_string()), carbon_token.vintage.to_string()),
+        (Field::Key("co2_tonnes".to_string()), carbon_token.co2_tonnes.to_string()),
+    ]
+}
+
+fn update_metadata_fields<'info>(
+    fields: Vec<(Field, String)>,
+    mint: &AccountInfo<'info>,
+    credit_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for (field, value) in fields {
+        let update_accounts = TokenMetadataUpdateField {
+            program_id: token_program.clone(),
+            metadata: mint.clone(),
+            update_authority: credit_authority.clone(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            update_accounts,
+            signer_seeds,
+        );
+
+        token_metadata_update_field(cpi_ctx, field, value)?;
+    }
+
+    Ok(())
+}
+
+// Tops up an account that has grown, such as a mint whose Token-2022 metadata got longer
+fn fund_rent_exemption<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let minimum_balance = Rent::get()?.minimum_balance(account.data_len());
+    let shortfall = minimum_balance.saturating_sub(account.lamports());
+
+    if shortfall > 0 {
+        let transfer_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            transfer_accounts,
+        );
+
+        transfer(cpi_ctx, shortfall)?;
+    }
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(name: String, symbol: String)]
 pub struct InitializeCarbonToken<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 4 + name.len() + 4 + symbol.len() + 4 + 200 + 8 + 4 + 50 + 8 + 4 + 100 + 8 + 1 + 1,
+        space = 8 + 32 + 32 + 4 + name.len() + 4 + symbol.len() + 4 + 200 + 8 + 2 + 4 + 50 + 8 + 2 + 4 + 100 + 8 + 8 + 8 + 8 + 1 + 1 + 1,
         seeds = [b"carbon_token", mint.key().as_ref()],
         bump
     )]
-    pub carbon_token: Account<'info, CarbonToken>,
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", hash(project.project_id.as_bytes()).as_ref()],
+        bump = project.bump,
+        constraint = project.status == ProjectStatus::Approved @ ErrorCode::ProjectNotApproved
+    )]
+    pub project: Box<Account<'info, Project>>,
     
+    // Transfers fail until credit_transfer_hook's initialize_extra_account_meta_list has run for the mint
     #[account(
         init,
         payer = payer,
         mint::decimals = 0,
-        mint::authority = authority,
+        mint::authority = credit_authority,
+        mint::freeze_authority = credit_authority,
         mint::token_program = token_program,
+        extensions::transfer_hook::authority = credit_authority,
+        extensions::transfer_hook::program_id = TRANSFER_HOOK_PROGRAM_ID,
+        extensions::metadata_pointer::authority = credit_authority,
+        extensions::metadata_pointer::metadata_address = mint,
+        extensions::permanent_delegate::delegate = credit_authority,
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
     
+    /// CHECK: Program PDA holding the mint authority, so issuance can only go through mint_carbon_credits
     #[account(
-        seeds = [b"user_role", b"MINT_AUTHORITY"],
+        seeds = [b"credit_authority", carbon_token.key().as_ref()],
+        bump
+    )]
+    pub credit_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"user_role", mint_authority_role.role_name.as_bytes()],
         bump = mint_authority_role.bump
     )]
     pub mint_authority_role: Account<'info, UserRole>,
     
+    #[account(
+        seeds = [b"role_membership", mint_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = mint_authority_membership.bump
+    )]
+    pub mint_authority_membership: Account<'info, RoleMembership>,
+    
     pub authority: Signer<'info>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String)]
+pub struct UpdateCarbonTokenMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"carbon_token", mint.key().as_ref()],
+        bump = carbon_token.bump,
+        realloc = 8 + 32 + 32 + 4 + name.len() + 4 + symbol.len() + 4 + 200 + 8 + 2 + 4 + 50 + 8 + 2 + 4 + 100 + 8 + 8 + 8 + 8 + 1 + 1 + 1,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Program PDA that signs as the metadata update authority
+    #[account(
+        seeds = [b"credit_authority", carbon_token.key().as_ref()],
+        bump = carbon_token.authority_bump
+    )]
+    pub credit_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"user_role", mint_authority_role.role_name.as_bytes()],
+        bump = mint_authority_role.bump
+    )]
+    pub mint_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", mint_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = mint_authority_membership.bump
+    )]
+    pub mint_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, monitoring_period_start: i64, monitoring_period_end: i64, serial_prefix: String)]
 pub struct MintCarbonCredits<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
     #[account(
         mut,
         seeds = [b"carbon_token", mint.key().as_ref()],
         bump = carbon_token.bump
     )]
-    pub carbon_token: Account<'info, CarbonToken>,
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 2 + 8 + 8 + 4 + serial_prefix.len() + 8 + 8 + 8 + 32 + 8 + 1,
+        seeds = [b"credit_batch", carbon_token.key().as_ref(), carbon_token.batch_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub batch: Box<Account<'info, CreditBatch>>,
+    
+    #[account(
+        address = carbon_token.project,
+        constraint = project.status == ProjectStatus::Approved @ ErrorCode::ProjectNotApproved
+    )]
+    pub project: Box<Account<'info, Project>>,
+    
+    /// CHECK: Created and reallocated by the handler, since every disjoint block grows it.
+    /// Keyed by hashes since project IDs and prefixes can outgrow the 32-byte seed limit
+    #[account(
+        mut,
+        seeds = [
+            b"serial_registry",
+            hash(carbon_token.project_id.as_bytes()).as_ref(),
+            hash(serial_prefix.as_bytes()).as_ref(),
+        ],
+        bump
+    )]
+    pub serial_registry: UncheckedAccount<'info>,
     
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
     
+    /// CHECK: Program PDA that signs as the mint authority
+    #[account(
+        seeds = [b"credit_authority", carbon_token.key().as_ref()],
+        bump = carbon_token.authority_bump
+    )]
+    pub credit_authority: UncheckedAccount<'info>,
+    
     #[account(
         mut,
         associated_token::mint = mint,
@@ -131,16 +779,162 @@ pub struct MintCarbonCredits<'info> {
     pub token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
-        seeds = [b"user_role", b"MINT_AUTHORITY"],
+        seeds = [b"user_role", mint_authority_role.role_name.as_bytes()],
         bump = mint_authority_role.bump
     )]
     pub mint_authority_role: Account<'info, UserRole>,
     
+    #[account(
+        seeds = [b"role_membership", mint_authority_role.role_name.as_bytes(), mint_authority.key().as_ref()],
+        bump = mint_authority_membership.bump
+    )]
+    pub mint_authority_membership: Account<'info, RoleMembership>,
+    
     /// CHECK: This is the recipient of the minted tokens
     pub recipient: AccountInfo<'info>,
     
     pub mint_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct IncreaseIssuance<'info> {
+    #[account(
+        mut,
+        seeds = [b"carbon_token", mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Program PDA that signs as the metadata update authority
+    #[account(
+        seeds = [b"credit_authority", carbon_token.key().as_ref()],
+        bump = carbon_token.authority_bump
+    )]
+    pub credit_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"user_role", verifier_role.role_name.as_bytes()],
+        bump = verifier_role.bump
+    )]
+    pub verifier_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", verifier_role.role_name.as_bytes(), verifier.key().as_ref()],
+        bump = verifier_membership.bump
+    )]
+    pub verifier_membership: Account<'info, RoleMembership>,
+    
+    pub verifier: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, beneficiary: String, reason: String)]
+pub struct RetireCredits<'info> {
+    #[account(
+        mut,
+        seeds = [b"carbon_token", mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Account<'info, CarbonToken>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 32 + 4 + beneficiary.len() + 4 + reason.len() + 8 + 2 + 4 + carbon_token.project_id.len() + 8 + 8 + 1,
+        seeds = [b"retirement", carbon_token.key().as_ref(), carbon_token.retirement_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub certificate: Account<'info, RetirementCertificate>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackCredits<'info> {
+    #[account(
+        mut,
+        seeds = [b"carbon_token", mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Program PDA that signs as the mint's permanent delegate and freeze authority
+    #[account(
+        seeds = [b"credit_authority", carbon_token.key().as_ref()],
+        bump = carbon_token.authority_bump
+    )]
+    pub credit_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Credits are moved here when provided, otherwise they are burned
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        seeds = [b"user_role", compliance_authority_role.role_name.as_bytes()],
+        bump = compliance_authority_role.bump
+    )]
+    pub compliance_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", compliance_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = compliance_authority_membership.bump
+    )]
+    pub compliance_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireCarbonToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"carbon_token", carbon_token.mint.as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Account<'info, CarbonToken>,
+}
@@ -0,0 +1,542 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, Burn, burn, TokenAccount, Mint};
+
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::{
+    CompliancePeriodCreated, CreditsSurrendered, CompliancePeriodClosed,
+    EmissionsAmendmentRequested, EmissionsAmendmentReviewed,
+};
+use crate::access_control::has_role;
+use crate::pda::create_pda_account;
+
+pub fn create_compliance_period(
+    ctx: Context<CreateCompliancePeriod>,
+    period_id: u64,
+    start_time: i64,
+    end_time: i64,
+    surrender_deadline: i64,
+    non_reporting_penalty_tonnes: u64,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    require!(
+        start_time < end_time && end_time <= surrender_deadline,
+        ErrorCode::InvalidCompliancePeriod
+    );
+    // A zero penalty would leave non-reporters non-compliant with nothing to slash
+    require!(non_reporting_penalty_tonnes > 0, ErrorCode::InvalidCompliancePeriod);
+
+    let period = &mut ctx.accounts.compliance_period;
+    period.period_id = period_id;
+    period.start_time = start_time;
+    period.end_time = end_time;
+    period.surrender_deadline = surrender_deadline;
+    period.non_reporting_penalty_tonnes = non_reporting_penalty_tonnes;
+    period.status = PeriodStatus::Open;
+    period.bump = ctx.bumps.compliance_period;
+
+    emit!(CompliancePeriodCreated {
+        period: period.key(),
+        period_id,
+        start_time,
+        end_time,
+        surrender_deadline,
+        non_reporting_penalty_tonnes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn surrender_credits(
+    ctx: Context<SurrenderCredits>,
+    amount: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Reporting), ErrorCode::ModulePaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.compliance_period.is_open_for_surrender(current_time),
+        ErrorCode::CompliancePeriodNotOpen
+    );
+
+    let industry = &ctx.accounts.industry;
+    require!(industry.is_active, ErrorCode::IndustryNotActive);
+    require!(industry.compliance_status != ComplianceStatus::Frozen, ErrorCode::IndustryFrozen);
+    require!(!ctx.accounts.carbon_token.is_expired(current_time), ErrorCode::CarbonTokenExpired);
+    require!(
+        ctx.accounts.controller.accepts_vintage(ctx.accounts.carbon_token.vintage),
+        ErrorCode::VintageNotAccepted
+    );
+
+    let burn_accounts = Burn {
+        mint: ctx.accounts.token_mint.to_account_info(),
+        from: ctx.accounts.industry_token_account.to_account_info(),
+        authority: ctx.accounts.industry_authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        burn_accounts,
+    );
+
+    burn(cpi_ctx, amount)?;
+
+    let carbon_token = &mut ctx.accounts.carbon_token;
+    carbon_token.total_supply = carbon_token.total_supply.checked_sub(amount).unwrap();
+
+    let industry = &mut ctx.accounts.industry;
+    industry.credits_burned = industry.credits_burned.checked_add(amount).unwrap();
+
+    let industry_period = &mut ctx.accounts.industry_period;
+    industry_period.industry = industry.key();
+    industry_period.period = ctx.accounts.compliance_period.key();
+    industry_period.credits_surrendered = industry_period.credits_surrendered.checked_add(amount).unwrap();
+    industry_period.bump = ctx.bumps.industry_period;
+
+    emit!(CreditsSurrendered {
+        industry: industry.authority,
+        period: industry_period.period,
+        mint: ctx.accounts.token_mint.key(),
+        amount,
+        period_surrendered: industry_period.credits_surrendered,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub fn amend_emissions_report(
+    ctx: Context<AmendEmissionsReport>,
+    proposed_co2_tonnes: u64,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Reporting), ErrorCode::ModulePaused);
+    let industry = &ctx.accounts.industry;
+    require!(industry.is_active, ErrorCode::IndustryNotActive);
+    require!(industry.compliance_status != ComplianceStatus::Frozen, ErrorCode::IndustryFrozen);
+    require!(
+        ctx.accounts.compliance_period.status == PeriodStatus::Open,
+        ErrorCode::CompliancePeriodNotOpen
+    );
+    require!(!ctx.accounts.industry_period.evaluated, ErrorCode::PeriodAlreadyEvaluated);
+
+    let report = &mut ctx.accounts.emissions_report;
+    require!(report.status == ReportStatus::Verified, ErrorCode::ReportNotVerified);
+    require!(!report.has_pending_amendment, ErrorCode::AmendmentPending);
+    require!(proposed_co2_tonnes != report.co2_tonnes, ErrorCode::InvalidAmount);
+
+    // Amendments are numbered per report, so the chain doubles as the report's history
+    let current_time = Clock::get()?.unix_timestamp;
+    let amendment = &mut ctx.accounts.emissions_amendment;
+    amendment.report = report.key();
+    amendment.amendment_number = report.amendment_count;
+    amendment.previous_co2_tonnes = report.co2_tonnes;
+    amendment.proposed_co2_tonnes = proposed_co2_tonnes;
+    amendment.reason_hash = reason_hash;
+    amendment.status = AmendmentStatus::Pending;
+    amendment.requested_at = current_time;
+    amendment.reviewer = None;
+    amendment.reviewed_at = 0;
+    amendment.bump = ctx.bumps.emissions_amendment;
+
+    report.amendment_count = report.amendment_count.checked_add(1).unwrap();
+    report.has_pending_amendment = true;
+
+    emit!(EmissionsAmendmentRequested {
+        report: report.key(),
+        amendment: amendment.key(),
+        amendment_number: amendment.amendment_number,
+        previous_co2_tonnes: amendment.previous_co2_tonnes,
+        proposed_co2_tonnes,
+        reason_hash,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub fn approve_emissions_amendment(ctx: Context<ReviewEmissionsAmendment>) -> Result<()> {
+    review_emissions_amendment(ctx, AmendmentStatus::Approved)
+}
+
+pub fn reject_emissions_amendment(ctx: Context<ReviewEmissionsAmendment>) -> Result<()> {
+    review_emissions_amendment(ctx, AmendmentStatus::Rejected)
+}
+
+fn review_emissions_amendment(ctx: Context<ReviewEmissionsAmendment>, status: AmendmentStatus) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.verifier_role,
+            &ctx.accounts.verifier_membership,
+            &ctx.accounts.verifier.key(),
+            Permission::VERIFY,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    // An industry holding the verifier role must not review its own amendment
+    require_keys_neq!(ctx.accounts.verifier.key(), ctx.accounts.industry.authority, ErrorCode::Unauthorized);
+    require!(
+        ctx.accounts.emissions_amendment.status == AmendmentStatus::Pending,
+        ErrorCode::AmendmentNotPending
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    if status == AmendmentStatus::Approved {
+        require!(
+            ctx.accounts.compliance_period.status == PeriodStatus::Open,
+            ErrorCode::CompliancePeriodNotOpen
+        );
+        // The industry must still be able to surrender against the amended figure
+        require!(
+            current_time <= ctx.accounts.compliance_period.surrender_deadline,
+            ErrorCode::SurrenderDeadlinePassed
+        );
+        require!(!ctx.accounts.industry_period.evaluated, ErrorCode::PeriodAlreadyEvaluated);
+
+        let previous = ctx.accounts.emissions_amendment.previous_co2_tonnes;
+        let proposed = ctx.accounts.emissions_amendment.proposed_co2_tonnes;
+
+        let industry = &mut ctx.accounts.industry;
+        industry.total_emissions = industry.total_emissions
+            .checked_sub(previous)
+            .unwrap()
+            .checked_add(proposed)
+            .unwrap();
+
+        ctx.accounts.emissions_report.co2_tonnes = proposed;
+        ctx.accounts.industry_period.emissions = proposed;
+    }
+
+    let amendment = &mut ctx.accounts.emissions_amendment;
+    amendment.status = status;
+    amendment.reviewer = Some(ctx.accounts.verifier.key());
+    amendment.reviewed_at = current_time;
+
+    let report = &mut ctx.accounts.emissions_report;
+    report.has_pending_amendment = false;
+
+    emit!(EmissionsAmendmentReviewed {
+        report: report.key(),
+        amendment: amendment.key(),
+        amendment_number: amendment.amendment_number,
+        status,
+        co2_tonnes: report.co2_tonnes,
+        verifier: ctx.accounts.verifier.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Remaining accounts come in (industry, industry_period) pairs, and a period closes exactly once.
+// Industries that never reported get their industry_period created here and are charged the
+// period's non-reporting penalty; unverified drafts owe at least what they declared.
+pub fn close_compliance_period<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseCompliancePeriod<'info>>,
+) -> Result<()> {
+    require!(ctx.accounts.controller.default_admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let period_key = ctx.accounts.compliance_period.key();
+    let period_end = ctx.accounts.compliance_period.end_time;
+    let non_reporting_penalty = ctx.accounts.compliance_period.non_reporting_penalty_tonnes;
+    require!(
+        ctx.accounts.compliance_period.status == PeriodStatus::Open,
+        ErrorCode::CompliancePeriodNotOpen
+    );
+    require!(
+        current_time > ctx.accounts.compliance_period.surrender_deadline,
+        ErrorCode::SurrenderDeadlineNotPassed
+    );
+
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), ErrorCode::InvalidRemainingAccounts);
+
+    let mut industries_evaluated: u32 = 0;
+    let mut industries_non_compliant: u32 = 0;
+
+    for accounts in pairs {
+        let [industry_info, industry_period_info] = accounts else {
+            return err!(ErrorCode::InvalidRemainingAccounts);
+        };
+
+        let mut industry = Account::<Industry>::try_from(industry_info)?;
+        // Industries onboarded after the period ended had no obligation for it
+        if industry.onboarding_date > period_end {
+            continue;
+        }
+
+        let (expected_industry_period, industry_period_bump) = Pubkey::find_program_address(
+            &[b"industry_period", period_key.as_ref(), industry_info.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(industry_period_info.key(), expected_industry_period, ErrorCode::InvalidRemainingAccounts);
+
+        let shortfall = if industry_period_info.data_is_empty() {
+            create_pda_account(
+                industry_period_info,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                8 + 32 + 32 + 8 + 8 + 1 + 1 + 1,
+                &[b"industry_period", period_key.as_ref(), industry_info.key.as_ref(), &[industry_period_bump]],
+            )?;
+
+            let industry_period = IndustryPeriod {
+                industry: industry_info.key(),
+                period: period_key,
+                emissions: 0,
+                credits_surrendered: 0,
+                report_verified: false,
+                evaluated: true,
+                bump: industry_period_bump,
+            };
+            industry_period.try_serialize(&mut &mut industry_period_info.try_borrow_mut_data()?[..])?;
+
+            non_reporting_penalty
+        } else {
+            let mut industry_period = Account::<IndustryPeriod>::try_from(industry_period_info)?;
+            if industry_period.evaluated {
+                continue;
+            }
+
+            industry_period.evaluated = true;
+            industry_period.exit(&crate::ID)?;
+
+            // An unverified draft is charged no less than it declared, so skipping the report
+            // never costs less than filing it
+            let owed = if industry_period.report_verified {
+                industry_period.emissions
+            } else {
+                industry_period.emissions.max(non_reporting_penalty)
+            };
+            owed.saturating_sub(industry_period.credits_surrendered)
+        };
+
+        industries_evaluated = industries_evaluated.checked_add(1).unwrap();
+
+        if shortfall > 0 {
+            industries_non_compliant = industries_non_compliant.checked_add(1).unwrap();
+            industry.shortfall_tonnes = industry.shortfall_tonnes.checked_add(shortfall).unwrap();
+
+            if industry.compliance_status != ComplianceStatus::Frozen {
+                industry.compliance_status = ComplianceStatus::NonCompliant;
+            }
+
+            industry.exit(&crate::ID)?;
+        }
+    }
+
+    let period = &mut ctx.accounts.compliance_period;
+    period.status = PeriodStatus::Closed;
+
+    emit!(CompliancePeriodClosed {
+        period: period_key,
+        period_id: period.period_id,
+        industries_evaluated,
+        industries_non_compliant,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(period_id: u64)]
+pub struct CreateCompliancePeriod<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1,
+        seeds = [b"compliance_period", period_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SurrenderCredits<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry", industry_authority.key().as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Box<Account<'info, Industry>>,
+    
+    #[account(
+        init_if_needed,
+        payer = industry_authority,
+        space = 8 + 32 + 32 + 8 + 8 + 1 + 1 + 1,
+        seeds = [b"industry_period", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump
+    )]
+    pub industry_period: Account<'info, IndustryPeriod>,
+    
+    #[account(
+        mut,
+        seeds = [b"carbon_token", token_mint.key().as_ref()],
+        bump = carbon_token.bump
+    )]
+    pub carbon_token: Box<Account<'info, CarbonToken>>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = industry_authority,
+        token::token_program = token_program,
+    )]
+    pub industry_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub industry_authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AmendEmissionsReport<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    #[account(
+        seeds = [b"industry", industry_authority.key().as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        mut,
+        seeds = [b"emissions_report", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = emissions_report.bump
+    )]
+    pub emissions_report: Account<'info, EmissionsReport>,
+    
+    #[account(
+        seeds = [b"industry_period", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = industry_period.bump
+    )]
+    pub industry_period: Account<'info, IndustryPeriod>,
+    
+    #[account(
+        init,
+        payer = industry_authority,
+        space = 8 + 32 + 4 + 8 + 8 + 32 + 1 + 8 + (1 + 32) + 8 + 1,
+        seeds = [b"emissions_amendment", emissions_report.key().as_ref(), emissions_report.amendment_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub emissions_amendment: Account<'info, EmissionsAmendment>,
+    
+    #[account(mut)]
+    pub industry_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewEmissionsAmendment<'info> {
+    #[account(
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry", industry.authority.as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        mut,
+        seeds = [b"emissions_report", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = emissions_report.bump
+    )]
+    pub emissions_report: Account<'info, EmissionsReport>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry_period", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = industry_period.bump
+    )]
+    pub industry_period: Account<'info, IndustryPeriod>,
+    
+    #[account(
+        mut,
+        seeds = [b"emissions_amendment", emissions_report.key().as_ref(), emissions_amendment.amendment_number.to_le_bytes().as_ref()],
+        bump = emissions_amendment.bump
+    )]
+    pub emissions_amendment: Account<'info, EmissionsAmendment>,
+    
+    #[account(
+        seeds = [b"user_role", verifier_role.role_name.as_bytes()],
+        bump = verifier_role.bump
+    )]
+    pub verifier_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", verifier_role.role_name.as_bytes(), verifier.key().as_ref()],
+        bump = verifier_membership.bump
+    )]
+    pub verifier_membership: Account<'info, RoleMembership>,
+    
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCompliancePeriod<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        mut,
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    pub admin: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
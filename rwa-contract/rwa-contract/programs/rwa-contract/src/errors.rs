@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+// Error codes follow declaration order, so new variants are only ever appended
 #[error_code]
 pub enum ErrorCode {
     #[msg("Unauthorized access")]
@@ -16,4 +17,90 @@ pub enum ErrorCode {
     AuctionExpired,
     #[msg("Insufficient tokens available in auction")]
     InsufficientTokensAvailable,
+    #[msg("Token mint does not match the carbon token")]
+    InvalidMint,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Invalid auction parameters")]
+    InvalidAuctionParameters,
+    #[msg("Quote mint accounts are required for this auction")]
+    QuoteAccountsRequired,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("User is not a member of this role")]
+    UserNotInRole,
+    #[msg("Role still has members assigned")]
+    RoleHasMembers,
+    #[msg("Unknown or empty permission set")]
+    InvalidPermissions,
+    #[msg("This module is paused")]
+    ModulePaused,
+    #[msg("Carbon credits have expired")]
+    CarbonTokenExpired,
+    #[msg("Carbon credits have not expired yet")]
+    CarbonTokenNotExpired,
+    #[msg("Minting would exceed the verified CO2 tonnage")]
+    IssuanceCapExceeded,
+    #[msg("Buffer holdback must not exceed 10000 basis points")]
+    InvalidBufferBps,
+    #[msg("Transfer hook invoked outside of a token transfer")]
+    NotTransferring,
+    #[msg("Carbon credits can only move between approved traders and KYC-verified industries")]
+    HolderNotApproved,
+    #[msg("Industry is frozen")]
+    IndustryFrozen,
+    #[msg("Industry is not frozen")]
+    IndustryNotFrozen,
+    #[msg("Remaining accounts do not match the expected layout")]
+    InvalidRemainingAccounts,
+    #[msg("Monitoring period must end after it starts")]
+    InvalidMonitoringPeriod,
+    #[msg("Serial block must be a non-empty range covering exactly the minted amount")]
+    InvalidSerialBlock,
+    #[msg("Serial block overlaps a range already issued for this project")]
+    SerialRangeOverlap,
+    #[msg("Vintage window bounds must be non-zero and the minimum must not exceed the maximum")]
+    InvalidVintageWindow,
+    #[msg("Carbon credit vintage is not accepted for compliance")]
+    VintageNotAccepted,
+    #[msg("Project id must be between 1 and 50 bytes")]
+    InvalidProjectId,
+    #[msg("Project is already in the requested status")]
+    InvalidProjectStatus,
+    #[msg("Project has not been approved")]
+    ProjectNotApproved,
+    #[msg("Bond token accounts are required for a mint-denominated bond")]
+    BondAccountsRequired,
+    #[msg("Bond is still within its lockup period")]
+    BondLocked,
+    #[msg("Industry must be compliant")]
+    IndustryNotCompliant,
+    #[msg("Amount exceeds the escrowed bond")]
+    InsufficientBond,
+    #[msg("Bond lockup must not be negative")]
+    InvalidBondConfig,
+    #[msg("Penalty price per tonne has not been configured")]
+    SlashingNotConfigured,
+    #[msg("Industry has no uncovered emissions the bond can pay for")]
+    NothingToSlash,
+    #[msg("Compliance period must start before it ends and end by its surrender deadline")]
+    InvalidCompliancePeriod,
+    #[msg("Compliance period is not open")]
+    CompliancePeriodNotOpen,
+    #[msg("Surrender deadline has not passed yet")]
+    SurrenderDeadlineNotPassed,
+    #[msg("Emissions report already has an amendment awaiting review")]
+    AmendmentPending,
+    #[msg("Emissions amendment has already been reviewed")]
+    AmendmentNotPending,
+    #[msg("Emissions for this period have already been evaluated")]
+    PeriodAlreadyEvaluated,
+    #[msg("Emissions report has already been verified")]
+    ReportAlreadyVerified,
+    #[msg("Emissions report has not been verified")]
+    ReportNotVerified,
+    #[msg("Adjusting a reported figure requires a reason hash")]
+    AdjustmentReasonRequired,
+    #[msg("The surrender deadline for this compliance period has passed")]
+    SurrenderDeadlinePassed,
 }
@@ -1,11 +1,163 @@
 use anchor_lang::prelude::*;
-use crate::state::ComplianceStatus;
+use crate::state::{AmendmentStatus, ClawbackReason, ComplianceStatus, PauseScope, ProjectStatus, RegistryStandard};
+
+#[event]
+pub struct AdminTransferProposed {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AcceptedVintagesUpdated {
+    pub min_accepted_vintage: Option<u16>,
+    pub max_accepted_vintage: Option<u16>,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondConfigUpdated {
+    pub bond_mint: Option<Pubkey>,
+    pub bond_lockup_seconds: i64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlashingConfigUpdated {
+    pub bond_mint: Option<Pubkey>,
+    pub penalty_per_tonne: u64,
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramPaused {
+    pub scope: PauseScope,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramUnpaused {
+    pub scope: PauseScope,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserRemovedFromRole {
+    pub role: Pubkey,
+    pub role_name: String,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RolePermissionsUpdated {
+    pub role: Pubkey,
+    pub role_name: String,
+    pub permissions: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleClosed {
+    pub role: Pubkey,
+    pub role_name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectRegistered {
+    pub project: Pubkey,
+    pub project_id: String,
+    pub developer: Pubkey,
+    pub registry_standard: RegistryStandard,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectStatusChanged {
+    pub project: Pubkey,
+    pub project_id: String,
+    pub status: ProjectStatus,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
 
 #[event]
 pub struct CarbonCreditsMinted {
     pub mint: Pubkey,
     pub recipient: Pubkey,
     pub amount: u64,
+    pub batch: Pubkey,
+    pub vintage: u16,
+    pub serial_prefix: String,
+    pub serial_start: u64,
+    pub serial_end: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuanceIncreased {
+    pub carbon_token: Pubkey,
+    pub verifier: Pubkey,
+    pub previous_co2_tonnes: u64,
+    pub new_co2_tonnes: u64,
+    pub attestation_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditsClawedBack {
+    pub carbon_token: Pubkey,
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub destination: Option<Pubkey>,
+    pub amount: u64,
+    pub reason: ClawbackReason,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CarbonTokenMetadataUpdated {
+    pub carbon_token: Pubkey,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CarbonTokenExpired {
+    pub carbon_token: Pubkey,
+    pub mint: Pubkey,
+    pub expiry_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditsRetired {
+    pub certificate: Pubkey,
+    pub mint: Pubkey,
+    pub retiree: Pubkey,
+    pub beneficiary: String,
+    pub reason: String,
+    pub amount: u64,
+    pub vintage: u16,
+    pub project_id: String,
+    pub serial: u64,
     pub timestamp: i64,
 }
 
@@ -17,13 +169,139 @@ pub struct IndustryOnboarded {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TraderApproved {
+    pub holder: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraderRevoked {
+    pub holder: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct EmissionsReported {
     pub industry: Pubkey,
+    pub period: Pubkey,
+    pub report: Pubkey,
+    pub co2_tonnes: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionsReportVerified {
+    pub industry: Pubkey,
+    pub period: Pubkey,
+    pub report: Pubkey,
+    pub submitted_co2_tonnes: u64,
+    pub verified_co2_tonnes: u64,
+    pub reason_hash: Option<[u8; 32]>,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionsAmendmentRequested {
+    pub report: Pubkey,
+    pub amendment: Pubkey,
+    pub amendment_number: u32,
+    pub previous_co2_tonnes: u64,
+    pub proposed_co2_tonnes: u64,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionsAmendmentReviewed {
+    pub report: Pubkey,
+    pub amendment: Pubkey,
+    pub amendment_number: u32,
+    pub status: AmendmentStatus,
     pub co2_tonnes: u64,
-    pub credits_burned: u64,
-    pub reporting_period: String,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompliancePeriodCreated {
+    pub period: Pubkey,
+    pub period_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub surrender_deadline: i64,
+    pub non_reporting_penalty_tonnes: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditsSurrendered {
+    pub industry: Pubkey,
+    pub period: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub period_surrendered: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompliancePeriodClosed {
+    pub period: Pubkey,
+    pub period_id: u64,
+    pub industries_evaluated: u32,
+    pub industries_non_compliant: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IndustryFrozen {
+    pub industry: Pubkey,
+    pub authority: Pubkey,
+    pub token_accounts_frozen: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IndustryUnfrozen {
+    pub industry: Pubkey,
+    pub authority: Pubkey,
+    pub token_accounts_thawed: u32,
+    pub compliance_status: ComplianceStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondDeposited {
+    pub industry: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub total_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondWithdrawn {
+    pub industry: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub total_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondSlashed {
+    pub industry: Pubkey,
+    pub bond_mint: Option<Pubkey>,
+    pub tonnes: u64,
+    pub penalty_per_tonne: u64,
+    pub penalty: u64,
+    pub total_bond: u64,
+    pub treasury: Pubkey,
     pub compliance_status: ComplianceStatus,
+    pub authority: Pubkey,
     pub timestamp: i64,
 }
 
@@ -35,6 +313,7 @@ pub struct DutchAuctionCreated {
     pub end_price: u64,
     pub duration_seconds: i64,
     pub tokens_for_sale: u64,
+    pub quote_mint: Option<Pubkey>,
     pub timestamp: i64,
 }
 
@@ -47,3 +326,13 @@ pub struct BidPlaced {
     pub total_cost: u64,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct AuctionFinalized {
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub tokens_sold: u64,
+    pub tokens_returned: u64,
+    pub cancelled: bool,
+    pub timestamp: i64,
+}
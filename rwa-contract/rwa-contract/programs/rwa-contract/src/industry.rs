@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::token_interface::{
+    TokenInterface, FreezeAccount, freeze_account, ThawAccount, thaw_account, TokenAccount, Mint,
+};
+
+use anchor_spl::associated_token::{AssociatedToken, Create, create_idempotent};
 
 use crate::state::*;
 use crate::errors::ErrorCode;
-use crate::events::{IndustryOnboarded, EmissionsReported};
+use crate::events::{
+    IndustryOnboarded, EmissionsReported, EmissionsReportVerified, IndustryFrozen, IndustryUnfrozen, BondDeposited,
+};
 use crate::access_control::has_role;
+use crate::bond::{deposit_bond, BondTokenAccounts};
 
 pub fn onboard_industry(
     ctx: Context<OnboardIndustry>,
@@ -12,22 +19,87 @@ pub fn onboard_industry(
     registration_number: String,
     bond_amount: u64,
 ) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Onboarding), ErrorCode::ModulePaused);
     require!(
-        has_role(&ctx.accounts.kyc_authority_role, &ctx.accounts.authority.key(), "KYC_AUTHORITY"),
+        has_role(
+            &ctx.accounts.kyc_authority_role,
+            &ctx.accounts.kyc_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::KYC,
+        ),
         ErrorCode::InsufficientPermissions
     );
 
+    let bond_mint = ctx.accounts.controller.configured_bond_mint();
+
+    // Mint-denominated bonds sit in the vault PDA's associated token account
+    if let Some(bond_mint) = bond_mint {
+        let (Some(mint), Some(vault_bond_account), Some(token_program), Some(associated_token_program)) = (
+            ctx.accounts.bond_mint.as_ref(),
+            ctx.accounts.vault_bond_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.associated_token_program.as_ref(),
+        ) else {
+            return err!(ErrorCode::BondAccountsRequired);
+        };
+        require_keys_eq!(mint.key(), bond_mint, ErrorCode::InvalidMint);
+
+        let create_accounts = Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: vault_bond_account.to_account_info(),
+            authority: ctx.accounts.bond_vault.to_account_info(),
+            mint: mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            associated_token_program.to_account_info(),
+            create_accounts,
+        );
+
+        create_idempotent(cpi_ctx)?;
+    }
+
+    if bond_amount > 0 {
+        deposit_bond(
+            bond_mint,
+            bond_amount,
+            &ctx.accounts.industry_authority,
+            &ctx.accounts.bond_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            BondTokenAccounts {
+                mint: ctx.accounts.bond_mint.as_deref(),
+                from: ctx.accounts.industry_bond_account.as_ref().map(|account| account.to_account_info()),
+                to: ctx.accounts.vault_bond_account.as_ref().map(|account| account.to_account_info()),
+                token_program: ctx.accounts.token_program.as_ref(),
+            },
+        )?;
+    }
+
+    let bond_vault = &mut ctx.accounts.bond_vault;
+    bond_vault.industry = ctx.accounts.industry.key();
+    bond_vault.bump = ctx.bumps.bond_vault;
+
+    let onboarding_date = Clock::get()?.unix_timestamp;
     let industry = &mut ctx.accounts.industry;
     industry.authority = ctx.accounts.industry_authority.key();
     industry.company_name = company_name;
     industry.registration_number = registration_number;
     industry.bond_amount = bond_amount;
+    industry.bond_mint = bond_mint;
+    industry.bond_locked_until = onboarding_date
+        .checked_add(ctx.accounts.controller.bond_lockup_seconds)
+        .unwrap();
     industry.is_kyc_verified = true;
     industry.is_active = true;
     industry.total_emissions = 0;
     industry.credits_burned = 0;
+    industry.shortfall_tonnes = 0;
+    industry.slashed_tonnes = 0;
+    industry.penalties_paid = 0;
     industry.compliance_status = ComplianceStatus::Compliant;
-    industry.onboarding_date = Clock::get()?.unix_timestamp;
+    industry.onboarding_date = onboarding_date;
     industry.bump = ctx.bumps.industry;
 
     emit!(IndustryOnboarded {
@@ -37,98 +109,463 @@ pub fn onboard_industry(
         timestamp: industry.onboarding_date,
     });
 
+    if bond_amount > 0 {
+        emit!(BondDeposited {
+            industry: industry.authority,
+            bond_mint,
+            amount: bond_amount,
+            total_bond: bond_amount,
+            timestamp: onboarding_date,
+        });
+    }
+
     Ok(())
 }
 
 pub fn report_emissions(
     ctx: Context<ReportEmissions>,
     co2_tonnes: u64,
-    reporting_period: String,
 ) -> Result<()> {
-    let industry = &mut ctx.accounts.industry;
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Reporting), ErrorCode::ModulePaused);
+    let industry = &ctx.accounts.industry;
     require!(industry.is_active, ErrorCode::IndustryNotActive);
+    require!(industry.compliance_status != ComplianceStatus::Frozen, ErrorCode::IndustryFrozen);
     require!(industry.authority == ctx.accounts.industry_authority.key(), ErrorCode::Unauthorized);
 
-    industry.total_emissions = industry.total_emissions.checked_add(co2_tonnes).unwrap();
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.compliance_period.is_open_for_reporting(current_time),
+        ErrorCode::CompliancePeriodNotOpen
+    );
+
+    let report = &mut ctx.accounts.emissions_report;
+    report.industry = industry.key();
+    report.period = ctx.accounts.compliance_period.key();
+    report.submitted_co2_tonnes = co2_tonnes;
+    report.co2_tonnes = co2_tonnes;
+    report.status = ReportStatus::Draft;
+    report.verifier = None;
+    report.adjustment_reason_hash = None;
+    report.reported_at = current_time;
+    report.verified_at = 0;
+    report.amendment_count = 0;
+    report.has_pending_amendment = false;
+    report.bump = ctx.bumps.emissions_report;
 
-    let current_balance = ctx.accounts.industry_token_account.amount;
-    
-    if current_balance >= co2_tonnes {
-        industry.credits_burned = industry.credits_burned.checked_add(co2_tonnes).unwrap();
-        
-        emit!(EmissionsReported {
-            industry: ctx.accounts.industry_authority.key(),
-            co2_tonnes,
-            credits_burned: co2_tonnes,
-            reporting_period,
-            compliance_status: ComplianceStatus::Compliant,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    // The period record may already exist if credits were surrendered before reporting. The
+    // declared figure is held there until a verifier replaces it with the attested one
+    let industry_period = &mut ctx.accounts.industry_period;
+    industry_period.industry = industry.key();
+    industry_period.period = report.period;
+    industry_period.emissions = co2_tonnes;
+    industry_period.bump = ctx.bumps.industry_period;
+
+    emit!(EmissionsReported {
+        industry: ctx.accounts.industry_authority.key(),
+        period: report.period,
+        report: report.key(),
+        co2_tonnes,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub fn verify_emissions_report(
+    ctx: Context<VerifyEmissionsReport>,
+    verified_co2_tonnes: u64,
+    reason_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.verifier_role,
+            &ctx.accounts.verifier_membership,
+            &ctx.accounts.verifier.key(),
+            Permission::VERIFY,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    // An industry holding the verifier role must not attest its own report
+    require_keys_neq!(ctx.accounts.verifier.key(), ctx.accounts.industry.authority, ErrorCode::Unauthorized);
+    require!(
+        ctx.accounts.compliance_period.status == PeriodStatus::Open,
+        ErrorCode::CompliancePeriodNotOpen
+    );
+    // The industry must still be able to surrender against the verified figure
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time <= ctx.accounts.compliance_period.surrender_deadline,
+        ErrorCode::SurrenderDeadlinePassed
+    );
+    require!(!ctx.accounts.industry_period.evaluated, ErrorCode::PeriodAlreadyEvaluated);
+
+    let report = &mut ctx.accounts.emissions_report;
+    require!(report.status == ReportStatus::Draft, ErrorCode::ReportAlreadyVerified);
+    require!(
+        verified_co2_tonnes == report.submitted_co2_tonnes || reason_hash.is_some(),
+        ErrorCode::AdjustmentReasonRequired
+    );
+
+    report.co2_tonnes = verified_co2_tonnes;
+    report.status = ReportStatus::Verified;
+    report.verifier = Some(ctx.accounts.verifier.key());
+    report.adjustment_reason_hash = reason_hash;
+    report.verified_at = current_time;
+
+    // Compliance is settled when the period closes, against the credits surrendered by then
+    let industry = &mut ctx.accounts.industry;
+    industry.total_emissions = industry.total_emissions.checked_add(verified_co2_tonnes).unwrap();
+
+    let industry_period = &mut ctx.accounts.industry_period;
+    industry_period.emissions = verified_co2_tonnes;
+    industry_period.report_verified = true;
+
+    emit!(EmissionsReportVerified {
+        industry: industry.authority,
+        period: report.period,
+        report: report.key(),
+        submitted_co2_tonnes: report.submitted_co2_tonnes,
+        verified_co2_tonnes,
+        reason_hash,
+        verifier: ctx.accounts.verifier.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub fn freeze_industry<'info>(ctx: Context<'_, '_, '_, 'info, SetIndustryFrozen<'info>>) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.freeze_authority_role,
+            &ctx.accounts.freeze_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::FREEZE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(
+        ctx.accounts.industry.compliance_status != ComplianceStatus::Frozen,
+        ErrorCode::IndustryFrozen
+    );
+
+    let token_accounts_frozen = set_token_accounts_frozen(
+        &ctx.accounts.industry.authority,
+        ctx.remaining_accounts,
+        &ctx.accounts.token_program.to_account_info(),
+        true,
+    )?;
+
+    let industry = &mut ctx.accounts.industry;
+    industry.compliance_status = ComplianceStatus::Frozen;
+
+    emit!(IndustryFrozen {
+        industry: industry.authority,
+        authority: ctx.accounts.authority.key(),
+        token_accounts_frozen,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn unfreeze_industry<'info>(ctx: Context<'_, '_, '_, 'info, SetIndustryFrozen<'info>>) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.freeze_authority_role,
+            &ctx.accounts.freeze_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::FREEZE,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(
+        ctx.accounts.industry.compliance_status == ComplianceStatus::Frozen,
+        ErrorCode::IndustryNotFrozen
+    );
+
+    let token_accounts_thawed = set_token_accounts_frozen(
+        &ctx.accounts.industry.authority,
+        ctx.remaining_accounts,
+        &ctx.accounts.token_program.to_account_info(),
+        false,
+    )?;
+
+    // Frozen overrides the evaluated status, so recompute it from shortfalls not yet paid for
+    let industry = &mut ctx.accounts.industry;
+    industry.compliance_status = if industry.unpenalized_tonnes() == 0 {
+        ComplianceStatus::Compliant
     } else {
-        industry.compliance_status = ComplianceStatus::NonCompliant;
-        
-        emit!(EmissionsReported {
-            industry: ctx.accounts.industry_authority.key(),
-            co2_tonnes,
-            credits_burned: current_balance,
-            reporting_period,
-            compliance_status: ComplianceStatus::NonCompliant,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-    }
+        ComplianceStatus::NonCompliant
+    };
+
+    emit!(IndustryUnfrozen {
+        industry: industry.authority,
+        authority: ctx.accounts.authority.key(),
+        token_accounts_thawed,
+        compliance_status: industry.compliance_status.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
 
+// Remaining accounts come in (token_account, mint, credit_authority) triples, one per
+// carbon token the industry holds
+fn set_token_accounts_frozen<'info>(
+    industry_authority: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: &AccountInfo<'info>,
+    freeze: bool,
+) -> Result<u32> {
+    let triples = remaining_accounts.chunks_exact(3);
+    require!(triples.remainder().is_empty(), ErrorCode::InvalidRemainingAccounts);
+
+    for accounts in triples {
+        let [token_account, mint, credit_authority] = accounts else {
+            return err!(ErrorCode::InvalidRemainingAccounts);
+        };
+
+        require_keys_eq!(*token_account.owner, token_program.key(), ErrorCode::InvalidRemainingAccounts);
+        let holding = TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?;
+        require_keys_eq!(holding.owner, *industry_authority, ErrorCode::Unauthorized);
+        require_keys_eq!(holding.mint, mint.key(), ErrorCode::InvalidMint);
+
+        let (carbon_token, _) = Pubkey::find_program_address(
+            &[b"carbon_token", mint.key.as_ref()],
+            &crate::ID,
+        );
+        let (expected_authority, authority_bump) = Pubkey::find_program_address(
+            &[b"credit_authority", carbon_token.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(credit_authority.key(), expected_authority, ErrorCode::InvalidRemainingAccounts);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"credit_authority",
+            carbon_token.as_ref(),
+            &[authority_bump],
+        ]];
+
+        if freeze {
+            let freeze_accounts = FreezeAccount {
+                account: token_account.clone(),
+                mint: mint.clone(),
+                authority: credit_authority.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.clone(),
+                freeze_accounts,
+                signer_seeds,
+            );
+
+            freeze_account(cpi_ctx)?;
+        } else {
+            let thaw_accounts = ThawAccount {
+                account: token_account.clone(),
+                mint: mint.clone(),
+                authority: credit_authority.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.clone(),
+                thaw_accounts,
+                signer_seeds,
+            );
+
+            thaw_account(cpi_ctx)?;
+        }
+    }
+
+    Ok((remaining_accounts.len() / 3) as u32)
+}
+
+// Industry PDAs are looked up by owner wherever the counterparty may not be an industry at all
+pub fn load_industry(industry: &AccountInfo) -> Result<Option<Industry>> {
+    if industry.owner != &crate::ID || industry.data_is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(Industry::try_deserialize(&mut &industry.try_borrow_data()?[..])?))
+}
+
+pub fn is_frozen(industry: &AccountInfo) -> Result<bool> {
+    Ok(load_industry(industry)?
+        .is_some_and(|industry| industry.compliance_status == ComplianceStatus::Frozen))
+}
+
 #[derive(Accounts)]
 #[instruction(company_name: String, registration_number: String)]
 pub struct OnboardIndustry<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
     #[account(
         init,
         payer = payer,
-        space = 8 + 32 + 4 + company_name.len() + 4 + registration_number.len() + 8 + 1 + 1 + 8 + 8 + 1 + 8 + 1,
+        space = 8 + 32 + 4 + company_name.len() + 4 + registration_number.len() + 8 + (1 + 32) + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1,
         seeds = [b"industry", industry_authority.key().as_ref()],
         bump
     )]
-    pub industry: Account<'info, Industry>,
+    pub industry: Box<Account<'info, Industry>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 1,
+        seeds = [b"bond_vault", industry.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    /// Required when the controller configures a bond mint; SOL bonds are used otherwise
+    pub bond_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub industry_bond_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// CHECK: Bond vault's associated token account, created in onboard_industry
+    #[account(mut)]
+    pub vault_bond_account: Option<UncheckedAccount<'info>>,
     
     #[account(
-        seeds = [b"user_role", b"KYC_AUTHORITY"],
+        seeds = [b"user_role", kyc_authority_role.role_name.as_bytes()],
         bump = kyc_authority_role.bump
     )]
     pub kyc_authority_role: Account<'info, UserRole>,
     
-    /// CHECK: This is the industry's authority public key
-    pub industry_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [b"role_membership", kyc_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = kyc_authority_membership.bump
+    )]
+    pub kyc_authority_membership: Account<'info, RoleMembership>,
+    
+    // Signs to fund the bond from its own wallet
+    #[account(mut)]
+    pub industry_authority: Signer<'info>,
     
     pub authority: Signer<'info>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ReportEmissions<'info> {
     #[account(
-        mut,
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    #[account(
         seeds = [b"industry", industry_authority.key().as_ref()],
         bump = industry.bump
     )]
     pub industry: Account<'info, Industry>,
     
+    // One report per industry and period; init fails for a second report
     #[account(
-        token::mint = token_mint,
-        token::authority = industry_authority,
-        token::token_program = token_program,
+        init,
+        payer = industry_authority,
+        space = 8 + 32 + 32 + 8 + 8 + 1 + (1 + 32) + (1 + 32) + 8 + 8 + 4 + 1 + 1,
+        seeds = [b"emissions_report", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump
     )]
-    pub industry_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub emissions_report: Account<'info, EmissionsReport>,
     
-    /// CHECK: This is the token mint for carbon credits
-    pub token_mint: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = industry_authority,
+        space = 8 + 32 + 32 + 8 + 8 + 1 + 1 + 1,
+        seeds = [b"industry_period", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump
+    )]
+    pub industry_period: Account<'info, IndustryPeriod>,
     
+    #[account(mut)]
     pub industry_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyEmissionsReport<'info> {
+    #[account(
+        seeds = [b"compliance_period", compliance_period.period_id.to_le_bytes().as_ref()],
+        bump = compliance_period.bump
+    )]
+    pub compliance_period: Account<'info, CompliancePeriod>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry", industry.authority.as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
     
-    /// CHECK: This account is used to specify the token program interface
-    pub token_program: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"emissions_report", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = emissions_report.bump
+    )]
+    pub emissions_report: Account<'info, EmissionsReport>,
+    
+    #[account(
+        mut,
+        seeds = [b"industry_period", compliance_period.key().as_ref(), industry.key().as_ref()],
+        bump = industry_period.bump
+    )]
+    pub industry_period: Account<'info, IndustryPeriod>,
+    
+    #[account(
+        seeds = [b"user_role", verifier_role.role_name.as_bytes()],
+        bump = verifier_role.bump
+    )]
+    pub verifier_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", verifier_role.role_name.as_bytes(), verifier.key().as_ref()],
+        bump = verifier_membership.bump
+    )]
+    pub verifier_membership: Account<'info, RoleMembership>,
+    
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetIndustryFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [b"industry", industry.authority.as_ref()],
+        bump = industry.bump
+    )]
+    pub industry: Account<'info, Industry>,
+    
+    #[account(
+        seeds = [b"user_role", freeze_authority_role.role_name.as_bytes()],
+        bump = freeze_authority_role.bump
+    )]
+    pub freeze_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", freeze_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = freeze_authority_membership.bump
+    )]
+    pub freeze_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
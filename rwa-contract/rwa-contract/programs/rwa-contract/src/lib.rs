@@ -1,17 +1,31 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 pub mod access_control;
 pub mod carbon_credits;
+pub mod project;
 pub mod industry;
+pub mod trader;
+pub mod bond;
+pub mod compliance;
 pub mod auction;
+pub mod transfer_hook;
+pub mod pda;
 pub mod state;
 pub mod events;
 pub mod errors;
 
 use access_control::*;
 use carbon_credits::*;
+use project::*;
 use industry::*;
+use trader::*;
+use bond::*;
+use compliance::*;
 use auction::*;
+use state::{ClawbackReason, PauseScope, RegistryStandard};
 
 declare_id!("4gBj3avgtDybWri9xiDQt7D3yaTiz3KbUysbVKq8Fcd4");
 
@@ -26,12 +40,52 @@ pub mod rwa_contract {
         access_control::initialize_access_control(ctx, default_admin)
     }
 
+    pub fn migrate_controller(ctx: Context<MigrateController>) -> Result<()> {
+        access_control::migrate_controller(ctx)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        access_control::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        access_control::accept_admin(ctx)
+    }
+
+    pub fn set_accepted_vintages(
+        ctx: Context<SetAcceptedVintages>,
+        min_accepted_vintage: Option<u16>,
+        max_accepted_vintage: Option<u16>,
+    ) -> Result<()> {
+        access_control::set_accepted_vintages(ctx, min_accepted_vintage, max_accepted_vintage)
+    }
+
+    pub fn set_bond_config(
+        ctx: Context<SetBondConfig>,
+        bond_mint: Option<Pubkey>,
+        bond_lockup_seconds: i64,
+    ) -> Result<()> {
+        access_control::set_bond_config(ctx, bond_mint, bond_lockup_seconds)
+    }
+
+    pub fn set_slashing_config(
+        ctx: Context<SetSlashingConfig>,
+        bond_mint: Option<Pubkey>,
+        penalty_per_tonne: u64,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        access_control::set_slashing_config(ctx, bond_mint, penalty_per_tonne, treasury)
+    }
+
     pub fn create_role(
         ctx: Context<CreateRole>,
         role_name: String,
-        actions: Vec<String>,
+        permissions: u32,
     ) -> Result<()> {
-        access_control::create_role(ctx, role_name, actions)
+        access_control::create_role(ctx, role_name, permissions)
     }
 
     pub fn assign_user_to_role(
@@ -41,14 +95,67 @@ pub mod rwa_contract {
         access_control::assign_user_to_role(ctx, user)
     }
 
+    pub fn remove_user_from_role(
+        ctx: Context<RemoveUserFromRole>,
+        user: Pubkey,
+    ) -> Result<()> {
+        access_control::remove_user_from_role(ctx, user)
+    }
+
+    pub fn update_role_permissions(
+        ctx: Context<UpdateRolePermissions>,
+        permissions: u32,
+    ) -> Result<()> {
+        access_control::update_role_permissions(ctx, permissions)
+    }
+
+    pub fn close_role(ctx: Context<CloseRole>) -> Result<()> {
+        access_control::close_role(ctx)
+    }
+
+    pub fn pause(
+        ctx: Context<SetPause>,
+        scope: PauseScope,
+    ) -> Result<()> {
+        access_control::pause(ctx, scope)
+    }
+
+    pub fn unpause(
+        ctx: Context<SetPause>,
+        scope: PauseScope,
+    ) -> Result<()> {
+        access_control::unpause(ctx, scope)
+    }
+
+    pub fn register_project(
+        ctx: Context<RegisterProject>,
+        project_id: String,
+        developer: Pubkey,
+        methodology: String,
+        country: String,
+        registry_standard: RegistryStandard,
+    ) -> Result<()> {
+        project::register_project(ctx, project_id, developer, methodology, country, registry_standard)
+    }
+
+    pub fn approve_project(ctx: Context<ReviewProject>) -> Result<()> {
+        project::approve_project(ctx)
+    }
+
+    pub fn suspend_project(ctx: Context<ReviewProject>) -> Result<()> {
+        project::suspend_project(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_carbon_token(
         ctx: Context<InitializeCarbonToken>,
         name: String,
         symbol: String,
         uri: String,
         co2_tonnes: u64,
-        project_id: String,
+        buffer_bps: u16,
         expiry_date: i64,
+        vintage: u16,
         issuer_name: String,
     ) -> Result<()> {
         carbon_credits::initialize_carbon_token(
@@ -57,17 +164,70 @@ pub mod rwa_contract {
             symbol,
             uri,
             co2_tonnes,
-            project_id,
+            buffer_bps,
             expiry_date,
+            vintage,
             issuer_name,
         )
     }
 
+    pub fn update_carbon_token_metadata(
+        ctx: Context<UpdateCarbonTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+        issuer_name: String,
+    ) -> Result<()> {
+        carbon_credits::update_carbon_token_metadata(ctx, name, symbol, uri, issuer_name)
+    }
+
     pub fn mint_carbon_credits(
         ctx: Context<MintCarbonCredits>,
         amount: u64,
+        monitoring_period_start: i64,
+        monitoring_period_end: i64,
+        serial_prefix: String,
+        serial_start: u64,
+        serial_end: u64,
     ) -> Result<()> {
-        carbon_credits::mint_carbon_credits(ctx, amount)
+        carbon_credits::mint_carbon_credits(
+            ctx,
+            amount,
+            monitoring_period_start,
+            monitoring_period_end,
+            serial_prefix,
+            serial_start,
+            serial_end,
+        )
+    }
+
+    pub fn increase_issuance(
+        ctx: Context<IncreaseIssuance>,
+        additional_tonnes: u64,
+        attestation_hash: [u8; 32],
+    ) -> Result<()> {
+        carbon_credits::increase_issuance(ctx, additional_tonnes, attestation_hash)
+    }
+
+    pub fn retire_credits(
+        ctx: Context<RetireCredits>,
+        amount: u64,
+        beneficiary: String,
+        reason: String,
+    ) -> Result<()> {
+        carbon_credits::retire_credits(ctx, amount, beneficiary, reason)
+    }
+
+    pub fn clawback_credits<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClawbackCredits<'info>>,
+        amount: u64,
+        reason: ClawbackReason,
+    ) -> Result<()> {
+        carbon_credits::clawback_credits(ctx, amount, reason)
+    }
+
+    pub fn expire_carbon_token(ctx: Context<ExpireCarbonToken>) -> Result<()> {
+        carbon_credits::expire_carbon_token(ctx)
     }
 
     pub fn onboard_industry(
@@ -79,16 +239,104 @@ pub mod rwa_contract {
         industry::onboard_industry(ctx, company_name, registration_number, bond_amount)
     }
 
+    pub fn approve_trader(ctx: Context<ApproveTrader>, holder: Pubkey) -> Result<()> {
+        trader::approve_trader(ctx, holder)
+    }
+
+    pub fn revoke_trader(ctx: Context<RevokeTrader>) -> Result<()> {
+        trader::revoke_trader(ctx)
+    }
+
     pub fn report_emissions(
         ctx: Context<ReportEmissions>,
         co2_tonnes: u64,
-        reporting_period: String,
     ) -> Result<()> {
-        industry::report_emissions(ctx, co2_tonnes, reporting_period)
+        industry::report_emissions(ctx, co2_tonnes)
     }
 
-    pub fn create_dutch_auction(
-        ctx: Context<CreateDutchAuction>,
+    pub fn verify_emissions_report(
+        ctx: Context<VerifyEmissionsReport>,
+        verified_co2_tonnes: u64,
+        reason_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        industry::verify_emissions_report(ctx, verified_co2_tonnes, reason_hash)
+    }
+
+    pub fn amend_emissions_report(
+        ctx: Context<AmendEmissionsReport>,
+        proposed_co2_tonnes: u64,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        compliance::amend_emissions_report(ctx, proposed_co2_tonnes, reason_hash)
+    }
+
+    pub fn approve_emissions_amendment(ctx: Context<ReviewEmissionsAmendment>) -> Result<()> {
+        compliance::approve_emissions_amendment(ctx)
+    }
+
+    pub fn reject_emissions_amendment(ctx: Context<ReviewEmissionsAmendment>) -> Result<()> {
+        compliance::reject_emissions_amendment(ctx)
+    }
+
+    pub fn create_compliance_period(
+        ctx: Context<CreateCompliancePeriod>,
+        period_id: u64,
+        start_time: i64,
+        end_time: i64,
+        surrender_deadline: i64,
+        non_reporting_penalty_tonnes: u64,
+    ) -> Result<()> {
+        compliance::create_compliance_period(
+            ctx,
+            period_id,
+            start_time,
+            end_time,
+            surrender_deadline,
+            non_reporting_penalty_tonnes,
+        )
+    }
+
+    pub fn surrender_credits(
+        ctx: Context<SurrenderCredits>,
+        amount: u64,
+    ) -> Result<()> {
+        compliance::surrender_credits(ctx, amount)
+    }
+
+    pub fn close_compliance_period<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseCompliancePeriod<'info>>,
+    ) -> Result<()> {
+        compliance::close_compliance_period(ctx)
+    }
+
+    pub fn top_up_bond(
+        ctx: Context<TopUpBond>,
+        amount: u64,
+    ) -> Result<()> {
+        bond::top_up_bond(ctx, amount)
+    }
+
+    pub fn withdraw_bond(
+        ctx: Context<WithdrawBond>,
+        amount: u64,
+    ) -> Result<()> {
+        bond::withdraw_bond(ctx, amount)
+    }
+
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        bond::slash_bond(ctx)
+    }
+
+    pub fn freeze_industry<'info>(ctx: Context<'_, '_, '_, 'info, SetIndustryFrozen<'info>>) -> Result<()> {
+        industry::freeze_industry(ctx)
+    }
+
+    pub fn unfreeze_industry<'info>(ctx: Context<'_, '_, '_, 'info, SetIndustryFrozen<'info>>) -> Result<()> {
+        industry::unfreeze_industry(ctx)
+    }
+
+    pub fn create_dutch_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateDutchAuction<'info>>,
         start_price: u64,
         end_price: u64,
         duration_seconds: i64,
@@ -97,10 +345,18 @@ pub mod rwa_contract {
         auction::create_dutch_auction(ctx, start_price, end_price, duration_seconds, tokens_for_sale)
     }
 
-    pub fn place_bid(
-        ctx: Context<PlaceBid>,
+    pub fn place_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
         token_amount: u64,
     ) -> Result<()> {
         auction::place_bid(ctx, token_amount)
     }
+
+    pub fn cancel_auction<'info>(ctx: Context<'_, '_, '_, 'info, CancelAuction<'info>>) -> Result<()> {
+        auction::cancel_auction(ctx)
+    }
+
+    pub fn finalize_auction<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeAuction<'info>>) -> Result<()> {
+        auction::finalize_auction(ctx)
+    }
 }
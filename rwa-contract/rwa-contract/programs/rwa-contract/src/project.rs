@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::{ProjectRegistered, ProjectStatusChanged};
+use crate::access_control::has_role;
+
+pub fn register_project(
+    ctx: Context<RegisterProject>,
+    project_id: String,
+    developer: Pubkey,
+    methodology: String,
+    country: String,
+    registry_standard: RegistryStandard,
+) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.project_authority_role,
+            &ctx.accounts.project_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::PROJECT,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+    require!(
+        !project_id.is_empty() && project_id.len() <= Project::MAX_PROJECT_ID_LEN,
+        ErrorCode::InvalidProjectId
+    );
+
+    let project = &mut ctx.accounts.project;
+    project.project_id = project_id;
+    project.developer = developer;
+    project.methodology = methodology;
+    project.country = country;
+    project.registry_standard = registry_standard;
+    project.status = ProjectStatus::Pending;
+    project.verifier = None;
+    project.carbon_token_count = 0;
+    project.registered_at = Clock::get()?.unix_timestamp;
+    project.bump = ctx.bumps.project;
+
+    emit!(ProjectRegistered {
+        project: project.key(),
+        project_id: project.project_id.clone(),
+        developer,
+        registry_standard,
+        authority: ctx.accounts.authority.key(),
+        timestamp: project.registered_at,
+    });
+
+    Ok(())
+}
+
+pub fn approve_project(ctx: Context<ReviewProject>) -> Result<()> {
+    set_project_status(ctx, ProjectStatus::Approved)
+}
+
+pub fn suspend_project(ctx: Context<ReviewProject>) -> Result<()> {
+    set_project_status(ctx, ProjectStatus::Suspended)
+}
+
+fn set_project_status(ctx: Context<ReviewProject>, status: ProjectStatus) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.verifier_role,
+            &ctx.accounts.verifier_membership,
+            &ctx.accounts.verifier.key(),
+            Permission::VERIFY,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let project = &mut ctx.accounts.project;
+    require!(project.status != status, ErrorCode::InvalidProjectStatus);
+
+    project.status = status;
+    project.verifier = Some(ctx.accounts.verifier.key());
+
+    emit!(ProjectStatusChanged {
+        project: project.key(),
+        project_id: project.project_id.clone(),
+        status,
+        verifier: ctx.accounts.verifier.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(project_id: String, developer: Pubkey, methodology: String, country: String)]
+pub struct RegisterProject<'info> {
+    // Keyed by a hash of the id so it fits in a seed; init fails for an id already registered
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 4 + project_id.len() + 32 + 4 + methodology.len() + 4 + country.len() + 1 + 1 + (1 + 32) + 4 + 8 + 1,
+        seeds = [b"project", hash(project_id.as_bytes()).as_ref()],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+    
+    #[account(
+        seeds = [b"user_role", project_authority_role.role_name.as_bytes()],
+        bump = project_authority_role.bump
+    )]
+    pub project_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", project_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = project_authority_membership.bump
+    )]
+    pub project_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewProject<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", hash(project.project_id.as_bytes()).as_ref()],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    
+    #[account(
+        seeds = [b"user_role", verifier_role.role_name.as_bytes()],
+        bump = verifier_role.bump
+    )]
+    pub verifier_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", verifier_role.role_name.as_bytes(), verifier.key().as_ref()],
+        bump = verifier_membership.bump
+    )]
+    pub verifier_membership: Account<'info, RoleMembership>,
+    
+    pub verifier: Signer<'info>,
+}
@@ -1,33 +1,191 @@
 use anchor_lang::prelude::*;
 
+// Every field is fixed-width so each one sits at the same offset whatever the values are;
+// a default Pubkey or a zero vintage stands for "unset"
 #[account]
 pub struct Controller {
     pub default_admin: Pubkey,
     pub bump: u8,
+    pub pending_admin: Pubkey,
+    pub paused: u8,
+    pub min_accepted_vintage: u16,
+    pub max_accepted_vintage: u16,
+    pub bond_mint: Pubkey,
+    pub bond_lockup_seconds: i64,
+    pub treasury: Pubkey,
+}
+
+impl Controller {
+    // New fields are appended so migrate_controller can grow existing accounts in place
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 1 + 2 + 2 + 32 + 8 + 32;
+
+    pub fn is_paused(&self, scope: PauseScope) -> bool {
+        self.paused & (PauseScope::All.bit() | scope.bit()) != 0
+    }
+
+    // An unset bound leaves that side of the window open
+    pub fn accepts_vintage(&self, vintage: u16) -> bool {
+        (self.min_accepted_vintage == 0 || vintage >= self.min_accepted_vintage)
+            && (self.max_accepted_vintage == 0 || vintage <= self.max_accepted_vintage)
+    }
+
+    // Bonds are posted in SOL unless a bond mint has been configured
+    pub fn configured_bond_mint(&self) -> Option<Pubkey> {
+        (self.bond_mint != Pubkey::default()).then_some(self.bond_mint)
+    }
 }
 
 #[account]
 pub struct UserRole {
     pub controller: Pubkey,
     pub role_name: String,
-    pub actions: Vec<String>,
-    pub users: Vec<Pubkey>,
+    pub permissions: Permission,
+    pub member_count: u32,
+    pub bump: u8,
+}
+
+#[account]
+pub struct RoleMembership {
+    pub role: Pubkey,
+    pub user: Pubkey,
+    pub assigned_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Project {
+    pub project_id: String,
+    pub developer: Pubkey,
+    pub methodology: String,
+    pub country: String,
+    pub registry_standard: RegistryStandard,
+    pub status: ProjectStatus,
+    pub verifier: Option<Pubkey>,
+    pub carbon_token_count: u32,
+    pub registered_at: i64,
     pub bump: u8,
 }
 
+impl Project {
+    // Matches the project_id allowance in CarbonToken's space
+    pub const MAX_PROJECT_ID_LEN: usize = 50;
+}
+
 #[account]
 pub struct CarbonToken {
     pub mint: Pubkey,
+    pub project: Pubkey,
     pub name: String,
     pub symbol: String,
     pub uri: String,
     pub co2_tonnes: u64,
+    pub buffer_bps: u16,
     pub project_id: String,
     pub expiry_date: i64,
+    pub vintage: u16,
     pub issuer_name: String,
     pub total_supply: u64,
+    pub total_issued: u64,
+    pub retirement_count: u64,
+    pub batch_count: u64,
     pub is_active: bool,
     pub bump: u8,
+    pub authority_bump: u8,
+}
+
+impl CarbonToken {
+    pub const MAX_BUFFER_BPS: u16 = 10_000;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expiry_date
+    }
+
+    // Tonnes that may be issued once the buffer pool holdback is set aside
+    pub fn issuance_cap(&self) -> u64 {
+        let issuable_bps = (Self::MAX_BUFFER_BPS - self.buffer_bps) as u128;
+        (self.co2_tonnes as u128 * issuable_bps / Self::MAX_BUFFER_BPS as u128) as u64
+    }
+}
+
+#[account]
+pub struct CreditBatch {
+    pub carbon_token: Pubkey,
+    pub mint: Pubkey,
+    pub batch_number: u64,
+    pub vintage: u16,
+    pub monitoring_period_start: i64,
+    pub monitoring_period_end: i64,
+    pub serial_prefix: String,
+    pub serial_start: u64,
+    pub serial_end: u64,
+    pub quantity: u64,
+    pub recipient: Pubkey,
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct SerialRegistry {
+    pub project_id: String,
+    pub serial_prefix: String,
+    pub issued_blocks: Vec<SerialBlock>,
+    pub bump: u8,
+}
+
+impl SerialRegistry {
+    pub const MAX_PREFIX_LEN: usize = 32;
+
+    pub fn space(&self) -> usize {
+        8 + 4 + self.project_id.len() + 4 + self.serial_prefix.len() + 4 + self.issued_blocks.len() * (8 + 8) + 1
+    }
+
+    // Blocks are kept sorted and merged with the ones they touch, so a new block can only
+    // overlap the first block that does not end before it. Returns false on overlap.
+    pub fn record_block(&mut self, start: u64, end: u64) -> bool {
+        let index = self.issued_blocks.partition_point(|block| block.end < start);
+        if self.issued_blocks.get(index).is_some_and(|next| next.start <= end) {
+            return false;
+        }
+
+        let joins_previous = index > 0 && self.issued_blocks[index - 1].end.checked_add(1) == Some(start);
+        let joins_next = self
+            .issued_blocks
+            .get(index)
+            .is_some_and(|next| end.checked_add(1) == Some(next.start));
+
+        match (joins_previous, joins_next) {
+            (true, true) => {
+                let next = self.issued_blocks.remove(index);
+                self.issued_blocks[index - 1].end = next.end;
+            }
+            (true, false) => self.issued_blocks[index - 1].end = end,
+            (false, true) => self.issued_blocks[index].start = start,
+            (false, false) => self.issued_blocks.insert(index, SerialBlock { start, end }),
+        }
+
+        true
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SerialBlock {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[account]
+pub struct RetirementCertificate {
+    pub carbon_token: Pubkey,
+    pub mint: Pubkey,
+    pub retiree: Pubkey,
+    pub beneficiary: String,
+    pub reason: String,
+    pub amount: u64,
+    pub vintage: u16,
+    pub project_id: String,
+    pub timestamp: i64,
+    pub serial: u64,
+    pub bump: u8,
 }
 
 #[account]
@@ -36,15 +194,113 @@ pub struct Industry {
     pub company_name: String,
     pub registration_number: String,
     pub bond_amount: u64,
+    pub bond_mint: Option<Pubkey>,
+    pub bond_locked_until: i64,
     pub is_kyc_verified: bool,
     pub is_active: bool,
     pub total_emissions: u64,
     pub credits_burned: u64,
+    pub shortfall_tonnes: u64,
+    pub slashed_tonnes: u64,
+    pub penalties_paid: u64,
     pub compliance_status: ComplianceStatus,
     pub onboarding_date: i64,
     pub bump: u8,
 }
 
+impl Industry {
+    // Shortfalls from closed compliance periods that have not yet been paid for out of the bond
+    pub fn unpenalized_tonnes(&self) -> u64 {
+        self.shortfall_tonnes.saturating_sub(self.slashed_tonnes)
+    }
+}
+
+#[account]
+pub struct ApprovedTrader {
+    pub holder: Pubkey,
+    pub approved_by: Pubkey,
+    pub approved_at: i64,
+    pub bump: u8,
+}
+
+// Holds SOL bonds as lamports above its rent reserve, and owns the token account for mint bonds
+#[account]
+pub struct BondVault {
+    pub industry: Pubkey,
+    pub bump: u8,
+}
+
+// Penalties are priced in the bond's own base units, so every bond denomination has its own.
+// SOL bonds are keyed by the default Pubkey
+#[account]
+pub struct BondPenalty {
+    pub bond_mint: Pubkey,
+    pub penalty_per_tonne: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct CompliancePeriod {
+    pub period_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub surrender_deadline: i64,
+    pub non_reporting_penalty_tonnes: u64,
+    pub status: PeriodStatus,
+    pub bump: u8,
+}
+
+impl CompliancePeriod {
+    pub fn is_open_for_reporting(&self, now: i64) -> bool {
+        self.status == PeriodStatus::Open && now >= self.start_time && now <= self.surrender_deadline
+    }
+
+    pub fn is_open_for_surrender(&self, now: i64) -> bool {
+        self.status == PeriodStatus::Open && now <= self.surrender_deadline
+    }
+}
+
+#[account]
+pub struct IndustryPeriod {
+    pub industry: Pubkey,
+    pub period: Pubkey,
+    pub emissions: u64,
+    pub credits_surrendered: u64,
+    pub report_verified: bool,
+    pub evaluated: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct EmissionsReport {
+    pub industry: Pubkey,
+    pub period: Pubkey,
+    pub submitted_co2_tonnes: u64,
+    pub co2_tonnes: u64,
+    pub status: ReportStatus,
+    pub verifier: Option<Pubkey>,
+    pub adjustment_reason_hash: Option<[u8; 32]>,
+    pub reported_at: i64,
+    pub verified_at: i64,
+    pub amendment_count: u32,
+    pub has_pending_amendment: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct EmissionsAmendment {
+    pub report: Pubkey,
+    pub amendment_number: u32,
+    pub previous_co2_tonnes: u64,
+    pub proposed_co2_tonnes: u64,
+    pub reason_hash: [u8; 32],
+    pub status: AmendmentStatus,
+    pub requested_at: i64,
+    pub reviewer: Option<Pubkey>,
+    pub reviewed_at: i64,
+    pub bump: u8,
+}
+
 #[account]
 pub struct DutchAuction {
     pub seller: Pubkey,
@@ -55,8 +311,104 @@ pub struct DutchAuction {
     pub end_time: i64,
     pub tokens_for_sale: u64,
     pub tokens_sold: u64,
+    pub quote_mint: Option<Pubkey>,
     pub is_active: bool,
     pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Permission(u32);
+
+impl Permission {
+    pub const MINT: Self = Self(1 << 0);
+    pub const KYC: Self = Self(1 << 1);
+    pub const AUCTION: Self = Self(1 << 2);
+    pub const FREEZE: Self = Self(1 << 3);
+    pub const PAUSE: Self = Self(1 << 4);
+    pub const VERIFY: Self = Self(1 << 5);
+    pub const COMPLIANCE: Self = Self(1 << 6);
+    pub const PROJECT: Self = Self(1 << 7);
+
+    const ALL: u32 = Self::MINT.0
+        | Self::KYC.0
+        | Self::AUCTION.0
+        | Self::FREEZE.0
+        | Self::PAUSE.0
+        | Self::VERIFY.0
+        | Self::COMPLIANCE.0
+        | Self::PROJECT.0;
+
+    /// Returns `None` for an empty set or any bit that is not a known permission.
+    pub fn from_bits(bits: u32) -> Option<Self> {
+        (bits != 0 && bits & !Self::ALL == 0).then_some(Self(bits))
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PauseScope {
+    All,
+    Minting,
+    Onboarding,
+    Reporting,
+    Auctions,
+}
+
+impl PauseScope {
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryStandard {
+    Verra,
+    GoldStandard,
+    ClimateActionReserve,
+    AmericanCarbonRegistry,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Pending,
+    Approved,
+    Suspended,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodStatus {
+    Open,
+    Closed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStatus {
+    Draft,
+    Verified,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AmendmentStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ClawbackReason {
+    ProjectInvalidated,
+    Fraud,
+    Reversal,
+    RegulatoryOrder,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -65,3 +417,185 @@ pub enum ComplianceStatus {
     NonCompliant,
     Frozen,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> Controller {
+        Controller {
+            default_admin: Pubkey::new_unique(),
+            bump: 255,
+            pending_admin: Pubkey::default(),
+            paused: 0,
+            min_accepted_vintage: 0,
+            max_accepted_vintage: 0,
+            bond_mint: Pubkey::default(),
+            bond_lockup_seconds: 0,
+            treasury: Pubkey::default(),
+        }
+    }
+
+    fn carbon_token(co2_tonnes: u64, buffer_bps: u16) -> CarbonToken {
+        CarbonToken {
+            mint: Pubkey::new_unique(),
+            project: Pubkey::new_unique(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            co2_tonnes,
+            buffer_bps,
+            project_id: String::new(),
+            expiry_date: 0,
+            vintage: 2024,
+            issuer_name: String::new(),
+            total_supply: 0,
+            total_issued: 0,
+            retirement_count: 0,
+            batch_count: 0,
+            is_active: true,
+            bump: 255,
+            authority_bump: 255,
+        }
+    }
+
+    fn compliance_period(status: PeriodStatus) -> CompliancePeriod {
+        CompliancePeriod {
+            period_id: 1,
+            start_time: 100,
+            end_time: 200,
+            surrender_deadline: 300,
+            non_reporting_penalty_tonnes: 50,
+            status,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn issuance_cap_sets_aside_the_buffer() {
+        assert_eq!(carbon_token(1_000, 0).issuance_cap(), 1_000);
+        assert_eq!(carbon_token(1_000, 2_500).issuance_cap(), 750);
+        assert_eq!(carbon_token(1_000, 10_000).issuance_cap(), 0);
+        assert_eq!(carbon_token(u64::MAX, 1).issuance_cap(), u64::MAX - u64::MAX / 10_000 - 1);
+    }
+
+    #[test]
+    fn permission_from_bits_rejects_empty_and_unknown_bits() {
+        assert!(Permission::from_bits(0).is_none());
+        assert!(Permission::from_bits(1 << 8).is_none());
+        assert!(Permission::from_bits(Permission::MINT.bits() | 1 << 31).is_none());
+        assert!(Permission::from_bits(Permission::KYC.bits()).is_some_and(|permissions| permissions == Permission::KYC));
+    }
+
+    #[test]
+    fn permission_contains_requires_every_bit() {
+        let permissions = Permission::from_bits(Permission::MINT.bits() | Permission::KYC.bits()).unwrap();
+        assert!(permissions.contains(Permission::MINT));
+        assert!(permissions.contains(Permission::KYC));
+        assert!(!permissions.contains(Permission::AUCTION));
+        assert!(!Permission::MINT.contains(permissions));
+    }
+
+    #[test]
+    fn pause_all_covers_every_module() {
+        let mut controller = controller();
+        assert!(!controller.is_paused(PauseScope::Minting));
+
+        controller.paused = PauseScope::Minting.bit();
+        assert!(controller.is_paused(PauseScope::Minting));
+        assert!(!controller.is_paused(PauseScope::Auctions));
+        assert!(!controller.is_paused(PauseScope::All));
+
+        controller.paused = PauseScope::All.bit();
+        assert!(controller.is_paused(PauseScope::Minting));
+        assert!(controller.is_paused(PauseScope::Reporting));
+        assert!(controller.is_paused(PauseScope::Auctions));
+    }
+
+    #[test]
+    fn vintage_window_bounds_are_inclusive_and_optional() {
+        let mut controller = controller();
+        assert!(controller.accepts_vintage(1990));
+        assert!(controller.accepts_vintage(u16::MAX));
+
+        controller.min_accepted_vintage = 2020;
+        assert!(!controller.accepts_vintage(2019));
+        assert!(controller.accepts_vintage(2020));
+        assert!(controller.accepts_vintage(2100));
+
+        controller.max_accepted_vintage = 2024;
+        assert!(controller.accepts_vintage(2024));
+        assert!(!controller.accepts_vintage(2025));
+
+        controller.min_accepted_vintage = 0;
+        assert!(controller.accepts_vintage(1990));
+        assert!(!controller.accepts_vintage(2025));
+    }
+
+    fn serial_registry() -> SerialRegistry {
+        SerialRegistry {
+            project_id: "VCS-1".to_string(),
+            serial_prefix: "VCS".to_string(),
+            issued_blocks: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn serial_blocks_may_arrive_out_of_order() {
+        let mut registry = serial_registry();
+        assert!(registry.record_block(500, 599));
+        assert!(registry.record_block(100, 199));
+        assert!(registry.record_block(300, 349));
+        assert_eq!(registry.issued_blocks.len(), 3);
+        assert_eq!(registry.issued_blocks[0].start, 100);
+        assert_eq!(registry.issued_blocks[2].start, 500);
+    }
+
+    #[test]
+    fn serial_blocks_reject_any_overlap() {
+        let mut registry = serial_registry();
+        assert!(registry.record_block(100, 199));
+        assert!(registry.record_block(300, 399));
+        assert!(!registry.record_block(199, 250));
+        assert!(!registry.record_block(250, 300));
+        assert!(!registry.record_block(150, 160));
+        assert!(!registry.record_block(0, 1_000));
+        assert!(!registry.record_block(399, 399));
+        assert_eq!(registry.issued_blocks.len(), 2);
+    }
+
+    #[test]
+    fn touching_serial_blocks_are_merged() {
+        let mut registry = serial_registry();
+        assert!(registry.record_block(100, 199));
+        assert!(registry.record_block(300, 399));
+        assert!(registry.record_block(200, 299));
+        assert_eq!(registry.issued_blocks.len(), 1);
+        assert_eq!((registry.issued_blocks[0].start, registry.issued_blocks[0].end), (100, 399));
+
+        assert!(registry.record_block(400, u64::MAX));
+        assert!(registry.record_block(0, 99));
+        assert_eq!(registry.issued_blocks.len(), 1);
+        assert_eq!((registry.issued_blocks[0].start, registry.issued_blocks[0].end), (0, u64::MAX));
+    }
+
+    #[test]
+    fn reporting_window_runs_from_start_to_surrender_deadline() {
+        let period = compliance_period(PeriodStatus::Open);
+        assert!(!period.is_open_for_reporting(99));
+        assert!(period.is_open_for_reporting(100));
+        assert!(period.is_open_for_reporting(300));
+        assert!(!period.is_open_for_reporting(301));
+        assert!(!compliance_period(PeriodStatus::Closed).is_open_for_reporting(150));
+    }
+
+    #[test]
+    fn surrender_window_closes_after_the_deadline() {
+        let period = compliance_period(PeriodStatus::Open);
+        assert!(period.is_open_for_surrender(0));
+        assert!(period.is_open_for_surrender(300));
+        assert!(!period.is_open_for_surrender(301));
+        assert!(!compliance_period(PeriodStatus::Closed).is_open_for_surrender(150));
+    }
+}
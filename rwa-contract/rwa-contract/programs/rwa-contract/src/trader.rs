@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::events::{TraderApproved, TraderRevoked};
+use crate::access_control::has_role;
+
+// Non-industry holders such as project developers and brokers; the transfer hook treats an
+// ApprovedTrader PDA like a KYC'd, compliant industry
+pub fn approve_trader(ctx: Context<ApproveTrader>, holder: Pubkey) -> Result<()> {
+    require!(!ctx.accounts.controller.is_paused(PauseScope::Onboarding), ErrorCode::ModulePaused);
+    require!(
+        has_role(
+            &ctx.accounts.kyc_authority_role,
+            &ctx.accounts.kyc_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::KYC,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    let approved_trader = &mut ctx.accounts.approved_trader;
+    approved_trader.holder = holder;
+    approved_trader.approved_by = ctx.accounts.authority.key();
+    approved_trader.approved_at = Clock::get()?.unix_timestamp;
+    approved_trader.bump = ctx.bumps.approved_trader;
+
+    emit!(TraderApproved {
+        holder,
+        authority: ctx.accounts.authority.key(),
+        timestamp: approved_trader.approved_at,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_trader(ctx: Context<RevokeTrader>) -> Result<()> {
+    require!(
+        has_role(
+            &ctx.accounts.kyc_authority_role,
+            &ctx.accounts.kyc_authority_membership,
+            &ctx.accounts.authority.key(),
+            Permission::KYC,
+        ),
+        ErrorCode::InsufficientPermissions
+    );
+
+    emit!(TraderRevoked {
+        holder: ctx.accounts.approved_trader.holder,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct ApproveTrader<'info> {
+    #[account(
+        seeds = [b"controller"],
+        bump = controller.bump
+    )]
+    pub controller: Account<'info, Controller>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"approved_trader", holder.as_ref()],
+        bump
+    )]
+    pub approved_trader: Account<'info, ApprovedTrader>,
+    
+    #[account(
+        seeds = [b"user_role", kyc_authority_role.role_name.as_bytes()],
+        bump = kyc_authority_role.bump
+    )]
+    pub kyc_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", kyc_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = kyc_authority_membership.bump
+    )]
+    pub kyc_authority_membership: Account<'info, RoleMembership>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeTrader<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"approved_trader", approved_trader.holder.as_ref()],
+        bump = approved_trader.bump
+    )]
+    pub approved_trader: Account<'info, ApprovedTrader>,
+    
+    #[account(
+        seeds = [b"user_role", kyc_authority_role.role_name.as_bytes()],
+        bump = kyc_authority_role.bump
+    )]
+    pub kyc_authority_role: Account<'info, UserRole>,
+    
+    #[account(
+        seeds = [b"role_membership", kyc_authority_role.role_name.as_bytes(), authority.key().as_ref()],
+        bump = kyc_authority_membership.bump
+    )]
+    pub kyc_authority_membership: Account<'info, RoleMembership>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
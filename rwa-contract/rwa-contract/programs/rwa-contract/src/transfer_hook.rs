@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::TransferChecked;
+
+// The credit_transfer_hook program, which Token-2022 calls on every carbon credit transfer
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("2FkdJLYh5Y2sD6n4MjfLg12BvaXwx7rWUU6zwU62CGY6");
+
+// Equivalent of token_interface::transfer_checked that also forwards the accounts the
+// mint's transfer hook needs, which callers pass through as remaining accounts
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    let mut account_infos = vec![
+        ctx.accounts.from.clone(),
+        ctx.accounts.mint.clone(),
+        ctx.accounts.to.clone(),
+        ctx.accounts.authority.clone(),
+    ];
+
+    spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi(
+        &mut instruction,
+        &mut account_infos,
+        &TRANSFER_HOOK_PROGRAM_ID,
+        ctx.accounts.from.clone(),
+        ctx.accounts.mint.clone(),
+        ctx.accounts.to.clone(),
+        ctx.accounts.authority.clone(),
+        amount,
+        &ctx.remaining_accounts,
+    )?;
+
+    invoke_signed(&instruction, &account_infos, ctx.signer_seeds)?;
+
+    Ok(())
+}
@@ -1,16 +1,661 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { AnchorError, BN, Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { expect } from "chai";
 import { RwaContract } from "../target/types/rwa_contract";
+import { CreditTransferHook } from "../target/types/credit_transfer_hook";
 
+// Runs against a fresh local validator: `anchor test --provider.cluster localnet`
 describe("rwa-contract", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.rwaContract as Program<RwaContract>;
+  const hook = anchor.workspace.creditTransferHook as Program<CreditTransferHook>;
+  const admin = provider.wallet.publicKey;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+  const ALL_PERMISSIONS = 0xff;
+  const ROLE_NAME = "OPERATOR";
+  const PROJECT_ID = "VCS-1234";
+
+  const seed = (value: string) => Buffer.from(value);
+  const sha256 = (value: string) => createHash("sha256").update(value).digest();
+  const u64 = (value: number | BN) => new BN(value).toArrayLike(Buffer, "le", 8);
+  const pda = (seeds: Buffer[], programId = program.programId) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const controller = pda([seed("controller")]);
+  const operatorRole = pda([seed("user_role"), seed(ROLE_NAME)]);
+  const operatorMembership = pda([seed("role_membership"), seed(ROLE_NAME), admin.toBuffer()]);
+  const project = pda([seed("project"), sha256(PROJECT_ID)]);
+
+  type CarbonTokenAccounts = {
+    mint: PublicKey;
+    carbonToken: PublicKey;
+    creditAuthority: PublicKey;
+  };
+
+  async function expectAnchorError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+    } catch (err) {
+      expect(err).to.be.instanceOf(AnchorError);
+      expect((err as AnchorError).error.errorCode.code).to.equal(code);
+      return;
+    }
+    expect.fail(`expected ${code}`);
+  }
+
+  async function createCarbonToken(co2Tonnes: number, bufferBps: number): Promise<CarbonTokenAccounts> {
+    const mint = Keypair.generate();
+    const carbonToken = pda([seed("carbon_token"), mint.publicKey.toBuffer()]);
+    const creditAuthority = pda([seed("credit_authority"), carbonToken.toBuffer()]);
+    const expiryDate = Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60;
+
+    await program.methods
+      .initializeCarbonToken(
+        "Carbon Credit Token",
+        "CCT",
+        "https://example.com/cct.json",
+        new BN(co2Tonnes),
+        bufferBps,
+        new BN(expiryDate),
+        2024,
+        "Green Energy Corp"
+      )
+      .accountsPartial({
+        carbonToken,
+        project,
+        mint: mint.publicKey,
+        creditAuthority,
+        mintAuthorityRole: operatorRole,
+        mintAuthorityMembership: operatorMembership,
+        authority: admin,
+        payer: admin,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([mint])
+      .rpc();
+
+    await hook.methods
+      .initializeExtraAccountMetaList()
+      .accountsPartial({
+        extraAccountMetaList: pda([seed("extra-account-metas"), mint.publicKey.toBuffer()], hook.programId),
+        mint: mint.publicKey,
+        carbonToken,
+        payer: admin,
+      })
+      .rpc();
+
+    return { mint: mint.publicKey, carbonToken, creditAuthority };
+  }
+
+  async function mintCredits(
+    token: CarbonTokenAccounts,
+    recipient: PublicKey,
+    serialPrefix: string,
+    serialStart: number,
+    serialEnd: number
+  ) {
+    const { batchCount } = await program.account.carbonToken.fetch(token.carbonToken);
+    const tokenAccount = getAssociatedTokenAddressSync(token.mint, recipient, true, TOKEN_2022_PROGRAM_ID);
+
+    return program.methods
+      .mintCarbonCredits(
+        new BN(serialEnd - serialStart + 1),
+        new BN(1_700_000_000),
+        new BN(1_731_536_000),
+        serialPrefix,
+        new BN(serialStart),
+        new BN(serialEnd)
+      )
+      .accountsPartial({
+        controller,
+        carbonToken: token.carbonToken,
+        batch: pda([seed("credit_batch"), token.carbonToken.toBuffer(), u64(batchCount)]),
+        project,
+        serialRegistry: pda([seed("serial_registry"), sha256(PROJECT_ID), sha256(serialPrefix)]),
+        mint: token.mint,
+        creditAuthority: token.creditAuthority,
+        tokenAccount,
+        mintAuthorityRole: operatorRole,
+        mintAuthorityMembership: operatorMembership,
+        recipient,
+        mintAuthority: admin,
+        payer: admin,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          admin,
+          tokenAccount,
+          recipient,
+          token.mint,
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ])
+      .rpc();
+  }
+
+  async function fund(to: PublicKey, lamports = LAMPORTS_PER_SOL) {
+    await provider.sendAndConfirm(
+      new Transaction().add(SystemProgram.transfer({ fromPubkey: admin, toPubkey: to, lamports }))
+    );
+  }
+
+  const industryPda = (authority: PublicKey) => pda([seed("industry"), authority.toBuffer()]);
+
+  async function onboardIndustry(companyName: string, bondLamports: number): Promise<Keypair> {
+    const authority = Keypair.generate();
+    await fund(authority.publicKey);
+
+    const industry = industryPda(authority.publicKey);
+    await program.methods
+      .onboardIndustry(companyName, `REG-${companyName}`, new BN(bondLamports))
+      .accountsPartial({
+        controller,
+        industry,
+        bondVault: pda([seed("bond_vault"), industry.toBuffer()]),
+        bondMint: null,
+        industryBondAccount: null,
+        vaultBondAccount: null,
+        kycAuthorityRole: operatorRole,
+        kycAuthorityMembership: operatorMembership,
+        industryAuthority: authority.publicKey,
+        authority: admin,
+        payer: admin,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+      })
+      .signers([authority])
+      .rpc();
+
+    return authority;
+  }
+
+  async function chainTime(): Promise<number> {
+    for (;;) {
+      const time = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      if (time !== null) {
+        return time;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+  }
+
+  async function waitUntilAfter(timestamp: number) {
+    while ((await chainTime()) <= timestamp) {
+      await new Promise((resolve) => setTimeout(resolve, 1_000));
+    }
+  }
+
+  before(async () => {
+    await program.methods
+      .initializeAccessControl(admin)
+      .accountsPartial({
+        controller,
+        program: program.programId,
+        programData: pda([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE),
+        payer: admin,
+      })
+      .rpc();
+
+    // A single role carrying every permission; instructions only look at the permission bits
+    await program.methods
+      .createRole(ROLE_NAME, ALL_PERMISSIONS)
+      .accountsPartial({ controller, userRole: operatorRole, admin, payer: admin })
+      .rpc();
+
+    await program.methods
+      .assignUserToRole(admin)
+      .accountsPartial({
+        controller,
+        userRole: operatorRole,
+        roleMembership: operatorMembership,
+        admin,
+        payer: admin,
+      })
+      .rpc();
+
+    await program.methods
+      .registerProject(PROJECT_ID, admin, "VM0042", "BR", { verra: {} })
+      .accountsPartial({
+        project,
+        projectAuthorityRole: operatorRole,
+        projectAuthorityMembership: operatorMembership,
+        authority: admin,
+        payer: admin,
+      })
+      .rpc();
+
+    await program.methods
+      .approveProject()
+      .accountsPartial({
+        project,
+        verifierRole: operatorRole,
+        verifierMembership: operatorMembership,
+        verifier: admin,
+      })
+      .rpc();
+  });
+
+  describe("issuance cap", () => {
+    const prefix = "VCS-1234-CAP";
+    let token: CarbonTokenAccounts;
+
+    before(async () => {
+      // 100 verified tonnes with a 20% buffer leaves 80 issuable credits
+      token = await createCarbonToken(100, 2_000);
+    });
+
+    it("mints up to the cap and no further", async () => {
+      await mintCredits(token, admin, prefix, 1, 60);
+      await expectAnchorError(mintCredits(token, admin, prefix, 61, 81), "IssuanceCapExceeded");
+
+      await mintCredits(token, admin, prefix, 61, 80);
+      await expectAnchorError(mintCredits(token, admin, prefix, 81, 81), "IssuanceCapExceeded");
+
+      const carbonToken = await program.account.carbonToken.fetch(token.carbonToken);
+      expect(carbonToken.totalIssued.toNumber()).to.equal(80);
+      expect(carbonToken.totalSupply.toNumber()).to.equal(80);
+    });
+  });
+
+  describe("serial blocks", () => {
+    const prefix = "VCS-1234-2024";
+    let token: CarbonTokenAccounts;
+
+    before(async () => {
+      token = await createCarbonToken(1_000, 0);
+    });
+
+    it("mints twice under the same serial prefix", async () => {
+      await mintCredits(token, admin, prefix, 1, 100);
+      await mintCredits(token, admin, prefix, 201, 300);
+
+      const carbonToken = await program.account.carbonToken.fetch(token.carbonToken);
+      expect(carbonToken.totalIssued.toNumber()).to.equal(200);
+      expect(carbonToken.batchCount.toNumber()).to.equal(2);
+    });
+
+    it("rejects blocks overlapping any issued range", async () => {
+      await expectAnchorError(mintCredits(token, admin, prefix, 250, 260), "SerialRangeOverlap");
+      await expectAnchorError(mintCredits(token, admin, prefix, 100, 110), "SerialRangeOverlap");
+    });
+
+    it("fills the gap between issued ranges", async () => {
+      await mintCredits(token, admin, prefix, 101, 200);
+      await expectAnchorError(mintCredits(token, admin, prefix, 150, 150), "SerialRangeOverlap");
+
+      const carbonToken = await program.account.carbonToken.fetch(token.carbonToken);
+      expect(carbonToken.totalIssued.toNumber()).to.equal(300);
+    });
+  });
+
+  describe("compliance period", () => {
+    const periodId = 1;
+    const penaltyPerTonne = 1_000;
+    const nonReportingPenaltyTonnes = 100;
+    const compliancePeriod = pda([seed("compliance_period"), u64(periodId)]);
+    const bondPenalty = pda([seed("bond_penalty"), PublicKey.default.toBuffer()]);
+
+    let token: CarbonTokenAccounts;
+    let reporter: Keypair;
+    let drafter: Keypair;
+    let absentee: Keypair;
+    let surrenderDeadline: number;
+
+    const emissionsReport = (authority: PublicKey) =>
+      pda([seed("emissions_report"), compliancePeriod.toBuffer(), industryPda(authority).toBuffer()]);
+    const industryPeriod = (authority: PublicKey) =>
+      pda([seed("industry_period"), compliancePeriod.toBuffer(), industryPda(authority).toBuffer()]);
+
+    const reportEmissions = (authority: Keypair, co2Tonnes: number) =>
+      program.methods
+        .reportEmissions(new BN(co2Tonnes))
+        .accountsPartial({
+          controller,
+          compliancePeriod,
+          industry: industryPda(authority.publicKey),
+          emissionsReport: emissionsReport(authority.publicKey),
+          industryPeriod: industryPeriod(authority.publicKey),
+          industryAuthority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    const verifyEmissionsReport = (authority: PublicKey, co2Tonnes: number) =>
+      program.methods
+        .verifyEmissionsReport(new BN(co2Tonnes), null)
+        .accountsPartial({
+          compliancePeriod,
+          industry: industryPda(authority),
+          emissionsReport: emissionsReport(authority),
+          industryPeriod: industryPeriod(authority),
+          verifierRole: operatorRole,
+          verifierMembership: operatorMembership,
+          verifier: admin,
+        })
+        .rpc();
+
+    const closeCompliancePeriod = () =>
+      program.methods
+        .closeCompliancePeriod()
+        .accountsPartial({ controller, compliancePeriod, admin, payer: admin })
+        .remainingAccounts(
+          [reporter, drafter, absentee].flatMap(({ publicKey }) =>
+            [industryPda(publicKey), industryPeriod(publicKey)].map((pubkey) => ({
+              pubkey,
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+        )
+        .rpc();
+
+    before(async () => {
+      token = await createCarbonToken(1_000, 0);
+      reporter = await onboardIndustry("Reporter", LAMPORTS_PER_SOL / 20);
+      drafter = await onboardIndustry("Drafter", LAMPORTS_PER_SOL / 20);
+      absentee = await onboardIndustry("Absentee", LAMPORTS_PER_SOL / 20);
+      await mintCredits(token, reporter.publicKey, "VCS-1234-COMPLIANCE", 1, 30);
+
+      await program.methods
+        .setSlashingConfig(null, new BN(penaltyPerTonne), admin)
+        .accountsPartial({ controller, bondPenalty, admin, payer: admin })
+        .rpc();
+
+      const now = await chainTime();
+      surrenderDeadline = now + 20;
+      await program.methods
+        .createCompliancePeriod(
+          new BN(periodId),
+          new BN(now - 60),
+          new BN(now + 10),
+          new BN(surrenderDeadline),
+          new BN(nonReportingPenaltyTonnes)
+        )
+        .accountsPartial({ controller, compliancePeriod, admin, payer: admin })
+        .rpc();
+    });
+
+    it("reports, verifies and surrenders within the period", async () => {
+      await reportEmissions(reporter, 50);
+      await verifyEmissionsReport(reporter.publicKey, 50);
+
+      await program.methods
+        .surrenderCredits(new BN(30))
+        .accountsPartial({
+          controller,
+          compliancePeriod,
+          industry: industryPda(reporter.publicKey),
+          industryPeriod: industryPeriod(reporter.publicKey),
+          carbonToken: token.carbonToken,
+          industryTokenAccount: getAssociatedTokenAddressSync(
+            token.mint,
+            reporter.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          tokenMint: token.mint,
+          industryAuthority: reporter.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([reporter])
+        .rpc();
+
+      // Left as a draft the verifier never reaches
+      await reportEmissions(drafter, 500);
+
+      const period = await program.account.industryPeriod.fetch(industryPeriod(reporter.publicKey));
+      expect(period.emissions.toNumber()).to.equal(50);
+      expect(period.creditsSurrendered.toNumber()).to.equal(30);
+      expect(period.reportVerified).to.be.true;
+    });
+
+    it("cannot close before the surrender deadline", async () => {
+      await expectAnchorError(closeCompliancePeriod(), "SurrenderDeadlineNotPassed");
+    });
+
+    it("refuses verification after the surrender deadline", async () => {
+      await waitUntilAfter(surrenderDeadline + 1);
+      await expectAnchorError(verifyEmissionsReport(drafter.publicKey, 500), "SurrenderDeadlinePassed");
+    });
+
+    it("charges shortfalls, drafts and non-reporters at close", async () => {
+      await closeCompliancePeriod();
+
+      const reporterIndustry = await program.account.industry.fetch(industryPda(reporter.publicKey));
+      expect(reporterIndustry.shortfallTonnes.toNumber()).to.equal(20);
+      expect(reporterIndustry.complianceStatus).to.deep.equal({ nonCompliant: {} });
+
+      // An unverified draft owes the larger of its declared tonnes and the non-reporting penalty
+      const drafterIndustry = await program.account.industry.fetch(industryPda(drafter.publicKey));
+      expect(drafterIndustry.shortfallTonnes.toNumber()).to.equal(500);
+
+      const absenteeIndustry = await program.account.industry.fetch(industryPda(absentee.publicKey));
+      expect(absenteeIndustry.shortfallTonnes.toNumber()).to.equal(nonReportingPenaltyTonnes);
+      expect(absenteeIndustry.complianceStatus).to.deep.equal({ nonCompliant: {} });
+
+      const absenteePeriod = await program.account.industryPeriod.fetch(industryPeriod(absentee.publicKey));
+      expect(absenteePeriod.evaluated).to.be.true;
+      expect(absenteePeriod.reportVerified).to.be.false;
+    });
+
+    it("closes only once", async () => {
+      await expectAnchorError(closeCompliancePeriod(), "CompliancePeriodNotOpen");
+    });
+
+    it("slashes the bond and restores compliance once the shortfall is paid", async () => {
+      const industry = industryPda(reporter.publicKey);
+      const bondVault = pda([seed("bond_vault"), industry.toBuffer()]);
+      const vaultBefore = await provider.connection.getBalance(bondVault);
+
+      await program.methods
+        .slashBond()
+        .accountsPartial({
+          controller,
+          industry,
+          bondVault,
+          bondPenalty,
+          treasury: admin,
+          bondMint: null,
+          vaultBondAccount: null,
+          treasuryBondAccount: null,
+          complianceAuthorityRole: operatorRole,
+          complianceAuthorityMembership: operatorMembership,
+          authority: admin,
+          tokenProgram: null,
+        })
+        .rpc();
+
+      const slashed = await program.account.industry.fetch(industry);
+      expect(slashed.slashedTonnes.toNumber()).to.equal(20);
+      expect(slashed.penaltiesPaid.toNumber()).to.equal(20 * penaltyPerTonne);
+      expect(slashed.complianceStatus).to.deep.equal({ compliant: {} });
+      expect(vaultBefore - (await provider.connection.getBalance(bondVault))).to.equal(20 * penaltyPerTonne);
+    });
+  });
+
+  describe("auctions", () => {
+    let token: CarbonTokenAccounts;
+    let seller: Keypair;
+    const trader = Keypair.generate();
+
+    const tokenAccount = (owner: PublicKey) =>
+      getAssociatedTokenAddressSync(token.mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    const tokenBalance = async (owner: PublicKey) =>
+      Number((await provider.connection.getTokenAccountBalance(tokenAccount(owner))).value.amount);
+    const auctionPda = () => pda([seed("dutch_auction"), seller.publicKey.toBuffer(), token.mint.toBuffer()]);
+    const vaultPda = (auction: PublicKey) => pda([seed("auction_vault"), auction.toBuffer()]);
+
+    // Extra accounts Token-2022 needs to run the transfer hook on a credit transfer
+    const hookAccounts = (source: PublicKey, destination: PublicKey) =>
+      [
+        pda([seed("extra-account-metas"), token.mint.toBuffer()], hook.programId),
+        hook.programId,
+        program.programId,
+        industryPda(source),
+        industryPda(destination),
+        source,
+        destination,
+        pda([seed("approved_trader"), source.toBuffer()]),
+        pda([seed("approved_trader"), destination.toBuffer()]),
+      ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+
+    async function createAuction(tokensForSale: number) {
+      const auction = auctionPda();
+      await program.methods
+        .createDutchAuction(new BN(1_000), new BN(500), new BN(60), new BN(tokensForSale))
+        .accountsPartial({
+          controller,
+          auction,
+          vault: vaultPda(auction),
+          auctionAuthorityRole: operatorRole,
+          auctionAuthorityMembership: operatorMembership,
+          seller: seller.publicKey,
+          sellerIndustry: industryPda(seller.publicKey),
+          sellerTokenAccount: tokenAccount(seller.publicKey),
+          tokenMint: token.mint,
+          carbonToken: token.carbonToken,
+          quoteMint: null,
+          authority: admin,
+          payer: admin,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(seller.publicKey, auction))
+        .signers([seller])
+        .rpc();
+      return auction;
+    }
+
+    const placeBid = (auction: PublicKey, tokenAmount: number) =>
+      program.methods
+        .placeBid(new BN(tokenAmount))
+        .accountsPartial({
+          controller,
+          auction,
+          vault: vaultPda(auction),
+          tokenMint: token.mint,
+          carbonToken: token.carbonToken,
+          bidderTokenAccount: tokenAccount(trader.publicKey),
+          bidderIndustry: industryPda(trader.publicKey),
+          seller: seller.publicKey,
+          sellerIndustry: industryPda(seller.publicKey),
+          quoteMint: null,
+          bidderQuoteAccount: null,
+          sellerQuoteAccount: null,
+          quoteTokenProgram: null,
+          bidder: trader.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(auction, trader.publicKey))
+        .signers([trader])
+        .rpc();
+
+    const cancelAuction = (auction: PublicKey, authority: Keypair, withRole: boolean) =>
+      program.methods
+        .cancelAuction()
+        .accountsPartial({
+          auction,
+          vault: vaultPda(auction),
+          tokenMint: token.mint,
+          sellerTokenAccount: tokenAccount(seller.publicKey),
+          seller: seller.publicKey,
+          sellerIndustry: industryPda(seller.publicKey),
+          auctionAuthorityRole: withRole ? operatorRole : null,
+          auctionAuthorityMembership: withRole ? operatorMembership : null,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(auction, seller.publicKey))
+        .signers([authority])
+        .rpc();
+
+    before(async () => {
+      token = await createCarbonToken(1_000, 0);
+      seller = await onboardIndustry("Seller", LAMPORTS_PER_SOL / 20);
+      await mintCredits(token, seller.publicKey, "VCS-1234-AUCTION", 1, 20);
+
+      await fund(trader.publicKey);
+      await program.methods
+        .approveTrader(trader.publicKey)
+        .accountsPartial({
+          controller,
+          approvedTrader: pda([seed("approved_trader"), trader.publicKey.toBuffer()]),
+          kycAuthorityRole: operatorRole,
+          kycAuthorityMembership: operatorMembership,
+          authority: admin,
+          payer: admin,
+        })
+        .rpc();
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            admin,
+            tokenAccount(trader.publicKey),
+            trader.publicKey,
+            token.mint,
+            TOKEN_2022_PROGRAM_ID
+          )
+        )
+      );
+    });
+
+    it("escrows credits and settles bids until the auction sells out", async () => {
+      const auction = await createAuction(10);
+      expect(await tokenBalance(seller.publicKey)).to.equal(10);
+      expect(Number((await provider.connection.getTokenAccountBalance(vaultPda(auction))).value.amount)).to.equal(10);
+
+      const sellerLamports = await provider.connection.getBalance(seller.publicKey);
+      await placeBid(auction, 4);
+      await expectAnchorError(placeBid(auction, 7), "InsufficientTokensAvailable");
+      await placeBid(auction, 6);
+
+      expect(await tokenBalance(trader.publicKey)).to.equal(10);
+      // The price decays from 1_000 towards 500 lamports per credit
+      expect((await provider.connection.getBalance(seller.publicKey)) - sellerLamports).to.be.within(
+        10 * 500,
+        10 * 1_000
+      );
+
+      await program.methods
+        .finalizeAuction()
+        .accountsPartial({
+          auction,
+          vault: vaultPda(auction),
+          tokenMint: token.mint,
+          sellerTokenAccount: tokenAccount(seller.publicKey),
+          seller: seller.publicKey,
+          sellerIndustry: industryPda(seller.publicKey),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(auction, seller.publicKey))
+        .rpc();
+
+      expect(await program.account.dutchAuction.fetchNullable(auction)).to.be.null;
+      expect(await provider.connection.getAccountInfo(vaultPda(auction))).to.be.null;
+    });
+
+    it("lets only the seller or an auction authority cancel, returning unsold credits", async () => {
+      const auction = await createAuction(5);
+      expect(await tokenBalance(seller.publicKey)).to.equal(5);
+
+      await expectAnchorError(cancelAuction(auction, trader, false), "InsufficientPermissions");
+
+      await cancelAuction(auction, seller, false);
+
+      expect(await tokenBalance(seller.publicKey)).to.equal(10);
+      expect(await program.account.dutchAuction.fetchNullable(auction)).to.be.null;
+    });
   });
 });